@@ -0,0 +1,718 @@
+//! Shared mollusk benchmark setup.
+//!
+//! Included by every changelog bench with `mod bench_support;`.
+//! A scenario is one changelog account plus one lookup instruction,
+//! adding a strategy or hit depth to a bench is one `Scenario` line.
+#![allow(dead_code)]
+
+use {
+    light_zero_copy::{cyclic_vec::ZeroCopyCyclicVecU64, ZeroCopyTraits},
+    mollusk_svm::Mollusk,
+    mollusk_svm_bencher::MolluskComputeUnitBencher,
+    optimize_cmp::{
+        changelog::{Entry, GenericChangelog, KeyValue, OwnerMint, OwnerMintEntry},
+        instruction::{unpack_lookup_result, CompositeLookupInstruction, LookupInstruction},
+        memory::AlignedBytes,
+        strategy::{CompositeStrategy, Strategy},
+    },
+    rand::rngs::StdRng,
+    rand::{Rng, SeedableRng},
+    solana_account::Account,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0x10, 0x11, 0x12,
+    0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20,
+]);
+
+pub const PROGRAM_PATH: &str = "./target/deploy/optimize_cmp";
+
+// Deterministic seed for consistent benchmark results
+pub const BENCHMARK_SEED: u64 = 9876543210987654321;
+
+/// Seeds of a bench run.
+///
+/// `--seed <u64>` or `BENCH_SEED` replaces `BENCHMARK_SEED`,
+/// `--seeds <n>` or `BENCH_SEEDS` runs every scenario with `n` consecutive
+/// seeds and reports min/median/max CU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedConfig {
+    pub seed: u64,
+    pub count: u64,
+}
+
+impl SeedConfig {
+    fn from_env() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let option = |flag: &str, env: &str| {
+            let value = args
+                .windows(2)
+                .find(|w| w[0] == flag)
+                .map(|w| w[1].clone())
+                .or_else(|| std::env::var(env).ok())?;
+            Some(
+                value
+                    .parse::<u64>()
+                    .unwrap_or_else(|e| panic!("{flag} / {env} `{value}`: {e}")),
+            )
+        };
+        Self {
+            seed: option("--seed", "BENCH_SEED").unwrap_or(BENCHMARK_SEED),
+            count: option("--seeds", "BENCH_SEEDS").unwrap_or(1).max(1),
+        }
+    }
+}
+
+pub fn seed_config() -> SeedConfig {
+    static CONFIG: std::sync::OnceLock<SeedConfig> = std::sync::OnceLock::new();
+    *CONFIG.get_or_init(SeedConfig::from_env)
+}
+
+/// Value stored with the target key.
+pub const TARGET_VALUE: u64 = 12345;
+
+pub fn create_random_mint(rng: &mut StdRng) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    rng.fill(&mut bytes);
+    bytes
+}
+
+/// Serializes a changelog with `entries` pushed in order, oldest first.
+pub fn changelog_data<T: KeyValue + ZeroCopyTraits>(
+    capacity: u64,
+    entries: impl IntoIterator<Item = T>,
+) -> AlignedBytes {
+    let mut backing_store = AlignedBytes::zeroed(
+        ZeroCopyCyclicVecU64::<T>::required_size_for_capacity(capacity),
+    );
+    let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+    for entry in entries {
+        changelog.push(entry);
+    }
+    backing_store
+}
+
+/// Changelog account with `entries` pushed in order, oldest first.
+///
+/// `Account::data` is a `Vec<u8>`, the runtime copies it to 8 byte aligned
+/// memory like account data on chain.
+pub fn changelog_account<T: KeyValue + ZeroCopyTraits>(
+    capacity: u64,
+    entries: impl IntoIterator<Item = T>,
+) -> Account {
+    Account {
+        lamports: 0,
+        data: changelog_data(capacity, entries).to_vec(),
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// How the other keys in the changelog relate to the target key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyDistribution {
+    /// Uniformly random keys.
+    #[default]
+    Random,
+    /// Keys share exactly the first `n` bytes with the target key,
+    /// like PDAs, vanity keys or keys ground to collide with a target.
+    /// `n` must be below 32, a 32 byte prefix is the target key itself.
+    SharedPrefix(usize),
+}
+
+impl KeyDistribution {
+    fn key(&self, rng: &mut StdRng, target_key: &[u8; 32]) -> [u8; 32] {
+        let mut key = create_random_mint(rng);
+        if let KeyDistribution::SharedPrefix(n) = *self {
+            key[..n].copy_from_slice(&target_key[..n]);
+            if key[n] == target_key[n] {
+                key[n] ^= 1;
+            }
+        }
+        key
+    }
+}
+
+/// Lookup instruction of a scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    /// `LOOKUP` or `LOOKUP_ENTRY` in an `Entry` changelog.
+    Strategy(Strategy),
+    /// `LOOKUP_COMPOSITE` in an `OwnerMintEntry` changelog where every entry
+    /// has the owner of the target key, like the changelog of one wallet.
+    Composite(CompositeStrategy),
+}
+
+impl Lookup {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lookup::Strategy(strategy) => strategy.name(),
+            Lookup::Composite(strategy) => strategy.name(),
+        }
+    }
+}
+
+impl From<Strategy> for Lookup {
+    fn from(strategy: Strategy) -> Self {
+        Lookup::Strategy(strategy)
+    }
+}
+
+impl From<CompositeStrategy> for Lookup {
+    fn from(strategy: CompositeStrategy) -> Self {
+        Lookup::Composite(strategy)
+    }
+}
+
+/// A changelog lookup to benchmark.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub capacity: u64,
+    /// Number of entries pushed, more than `capacity` wraps the ring.
+    pub pushes: u64,
+    /// Position of the target key counted from the newest entry (0 = newest).
+    /// `None` searches for a key that is not in the changelog.
+    pub hit_depth: Option<u64>,
+    pub lookup: Lookup,
+    pub num_iters: Option<u32>,
+    /// Looks up through the `Entry` fast path of the strategy.
+    pub entry_fast_path: bool,
+    pub seed: u64,
+    pub keys: KeyDistribution,
+    pub name: Option<String>,
+}
+
+impl Scenario {
+    /// Ring wrapped half way, so lookups cross the wrap point.
+    pub fn new(capacity: u64, hit_depth: Option<u64>, lookup: impl Into<Lookup>) -> Self {
+        Self {
+            capacity,
+            pushes: capacity + capacity / 2,
+            hit_depth,
+            lookup: lookup.into(),
+            num_iters: None,
+            entry_fast_path: false,
+            seed: seed_config().seed,
+            keys: KeyDistribution::Random,
+            name: None,
+        }
+    }
+
+    pub fn pushes(mut self, pushes: u64) -> Self {
+        self.pushes = pushes;
+        self
+    }
+
+    pub fn num_iters(mut self, num_iters: u32) -> Self {
+        self.num_iters = Some(num_iters);
+        self
+    }
+
+    pub fn entry_fast_path(mut self) -> Self {
+        self.entry_fast_path = true;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn keys(mut self, keys: KeyDistribution) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Overrides the generated bench name.
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Number of entries the lookup may compare.
+    fn limit(&self) -> u64 {
+        let len = self.pushes.min(self.capacity);
+        self.num_iters.map_or(len, |n| (n as u64).min(len))
+    }
+
+    /// Number of entries compared before the lookup returns.
+    pub fn comparisons(&self) -> u64 {
+        match self.hit_depth {
+            Some(depth) if depth < self.limit() => depth + 1,
+            _ => self.limit(),
+        }
+    }
+
+    /// Value the lookup must return.
+    pub fn expected(&self) -> Option<u64> {
+        self.hit_depth
+            .filter(|depth| *depth < self.limit())
+            .map(|_| TARGET_VALUE)
+    }
+
+    pub fn bench_name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        let mut name = format!("{}_cap{}", self.lookup.name(), self.capacity);
+        match self.hit_depth {
+            Some(depth) => name.push_str(&format!("_depth{depth}")),
+            None => name.push_str("_not_found"),
+        }
+        if let Some(num_iters) = self.num_iters {
+            name.push_str(&format!("_iters{num_iters}"));
+        }
+        if let KeyDistribution::SharedPrefix(n) = self.keys {
+            name.push_str(&format!("_prefix{n}"));
+        }
+        if self.entry_fast_path {
+            name.push_str("_entry");
+        }
+        name
+    }
+
+    /// Builds the changelog account and lookup instruction.
+    pub fn build(&self) -> BenchCase {
+        assert!(
+            self.hit_depth
+                .is_none_or(|d| d < self.pushes.min(self.capacity)),
+            "{}: hit depth outside of the changelog",
+            self.bench_name()
+        );
+        if let KeyDistribution::SharedPrefix(n) = self.keys {
+            assert!(
+                n < 32,
+                "{}: shared prefix must be shorter than the 32 byte key",
+                self.bench_name()
+            );
+        }
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let target_key = create_random_mint(&mut rng);
+        let target_index = self.hit_depth.map(|depth| self.pushes - 1 - depth);
+        // (mint, value) of every pushed entry
+        let entries: Vec<([u8; 32], u64)> = (0..self.pushes)
+            .map(|i| {
+                if Some(i) == target_index {
+                    (target_key, TARGET_VALUE)
+                } else {
+                    (self.keys.key(&mut rng, &target_key), rng.gen())
+                }
+            })
+            .collect();
+        let (account, instruction_data) = match self.lookup {
+            Lookup::Strategy(strategy) => {
+                let entries = entries
+                    .into_iter()
+                    .map(|(mint, value)| Entry::new(mint, value));
+                let mut lookup = LookupInstruction::new(strategy, self.num_iters, target_key);
+                lookup.entry_fast_path = self.entry_fast_path;
+                (changelog_account(self.capacity, entries), lookup.pack())
+            }
+            Lookup::Composite(strategy) => {
+                assert!(
+                    !self.entry_fast_path,
+                    "{}: composite lookups have no entry fast path",
+                    self.bench_name()
+                );
+                let owner = create_random_mint(&mut rng);
+                let entries = entries.into_iter().map(|(mint, value)| OwnerMintEntry {
+                    value,
+                    key: OwnerMint { owner, mint },
+                });
+                let key = OwnerMint {
+                    owner,
+                    mint: target_key,
+                };
+                let lookup = CompositeLookupInstruction::new(strategy, self.num_iters, key);
+                (changelog_account(self.capacity, entries), lookup.pack())
+            }
+        };
+        BenchCase::with_instruction_data(
+            self.bench_name(),
+            self.lookup,
+            instruction_data,
+            target_key,
+            self.comparisons(),
+            self.expected(),
+            account,
+        )
+    }
+}
+
+impl Scenario {
+    /// Runs the scenario once per configured seed, starting at its own seed,
+    /// and returns the consumed CU sorted ascending.
+    pub fn run_seeds(&self, mollusk: &Mollusk) -> Vec<u64> {
+        let mut cus: Vec<u64> = (0..seed_config().count)
+            .map(|i| {
+                let seed = self.seed.wrapping_add(i);
+                let mut case = self.clone().seed(seed).build();
+                case.name = format!("{} seed {seed}", case.name);
+                case.run(mollusk)
+            })
+            .collect();
+        cus.sort_unstable();
+        cus
+    }
+
+    /// Median CU over the configured seeds, for benches that report one
+    /// number per scenario.
+    pub fn median_cu(&self, mollusk: &Mollusk) -> u64 {
+        let cus = self.run_seeds(mollusk);
+        cus[cus.len() / 2]
+    }
+}
+
+/// Instruction and accounts of one bench.
+pub struct BenchCase {
+    pub name: String,
+    pub lookup: Lookup,
+    /// Number of entries compared before the lookup returns.
+    pub comparisons: u64,
+    /// Value the lookup must return.
+    pub expected: Option<u64>,
+    /// Key searched by the instruction, the mint of a composite key.
+    pub key: [u8; 32],
+    pub instruction: Instruction,
+    pub accounts: Vec<(Pubkey, Account)>,
+}
+
+impl BenchCase {
+    pub fn new(
+        name: String,
+        lookup: LookupInstruction,
+        comparisons: u64,
+        expected: Option<u64>,
+        changelog: Account,
+    ) -> Self {
+        Self::with_instruction_data(
+            name,
+            Strategy::try_from(lookup.strategy).unwrap().into(),
+            lookup.pack(),
+            lookup.key,
+            comparisons,
+            expected,
+            changelog,
+        )
+    }
+
+    fn with_instruction_data(
+        name: String,
+        lookup: Lookup,
+        instruction_data: Vec<u8>,
+        key: [u8; 32],
+        comparisons: u64,
+        expected: Option<u64>,
+        changelog: Account,
+    ) -> Self {
+        let changelog_pubkey = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &instruction_data,
+            vec![AccountMeta::new_readonly(changelog_pubkey, false)],
+        );
+        Self {
+            name,
+            lookup,
+            comparisons,
+            expected,
+            key,
+            instruction,
+            accounts: vec![(changelog_pubkey, changelog)],
+        }
+    }
+
+    /// Runs the lookup, checks the returned value and returns the consumed CU.
+    pub fn run(&self, mollusk: &Mollusk) -> u64 {
+        let result = mollusk.process_instruction(&self.instruction, &self.accounts);
+        assert!(
+            result.raw_result.is_ok(),
+            "{}: {:?}",
+            self.name,
+            result.raw_result
+        );
+        assert_eq!(
+            unpack_lookup_result(&result.return_data),
+            Ok(self.expected),
+            "{}: wrong lookup result",
+            self.name
+        );
+        result.compute_units_consumed
+    }
+}
+
+pub fn mollusk() -> Mollusk {
+    Mollusk::new(&PROGRAM_ID, PROGRAM_PATH)
+}
+
+/// Checks every lookup result, then runs all cases with
+/// `MolluskComputeUnitBencher`, output in `target/benches`.
+pub fn run_benches(cases: &[BenchCase]) {
+    // Disable logging for cleaner benchmark output
+    solana_logger::setup_with("");
+
+    let mollusk = mollusk();
+    for case in cases {
+        case.run(&mollusk);
+    }
+
+    let mut bencher = MolluskComputeUnitBencher::new(mollusk);
+    for case in cases {
+        bencher = bencher.bench((case.name.as_str(), &case.instruction, &case.accounts));
+    }
+    bencher.must_pass(true).out_dir("target/benches").execute();
+}
+
+/// Builds and runs all scenarios.
+///
+/// With more than one seed every scenario runs once per seed, starting at its
+/// own seed, and min/median/max CU go to `target/benches/{bench}_seeds.md`.
+pub fn run_scenarios(bench: &str, scenarios: &[Scenario]) {
+    let config = seed_config();
+    if config.count == 1 {
+        let cases: Vec<BenchCase> = scenarios.iter().map(Scenario::build).collect();
+        run_benches(&cases);
+        return;
+    }
+
+    solana_logger::setup_with("");
+    let mollusk = mollusk();
+    let mut report = format!(
+        "# {bench}, {} seeds from {}\n\n| Name | Min CUs | Median CUs | Max CUs |\n|------|---------|------------|---------|\n",
+        config.count, config.seed
+    );
+    for scenario in scenarios {
+        let cus = scenario.run_seeds(&mollusk);
+        report.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            scenario.bench_name(),
+            cus[0],
+            cus[cus.len() / 2],
+            cus[cus.len() - 1]
+        ));
+    }
+    println!("{report}");
+    std::fs::create_dir_all("target/benches").unwrap();
+    std::fs::write(format!("target/benches/{bench}_seeds.md"), report).unwrap();
+}
+
+const CHANGELOG_CU_CAPACITY: u64 = 1000;
+
+/// Target key is the 10th newest entry, found on the last allowed iteration.
+fn after_10(lookup: impl Into<Lookup>) -> Scenario {
+    Scenario::new(CHANGELOG_CU_CAPACITY, Some(9), lookup).num_iters(10)
+}
+
+/// Target key is the 100th newest entry, found on the last allowed iteration.
+fn after_100(lookup: impl Into<Lookup>) -> Scenario {
+    Scenario::new(CHANGELOG_CU_CAPACITY, Some(99), lookup).num_iters(100)
+}
+
+/// Scans all 1000 entries.
+fn not_found(lookup: impl Into<Lookup>) -> Scenario {
+    Scenario::new(CHANGELOG_CU_CAPACITY, None, lookup)
+}
+
+/// Scenarios of the `changelog_cu` bench and the README table.
+#[rustfmt::skip]
+pub fn changelog_cu_scenarios() -> Vec<Scenario> {
+    vec![
+        // PartialEq comparison
+        after_10(Strategy::PartialEq).named("find_after_10_iterations_partialeq"),
+        after_100(Strategy::PartialEq).named("find_after_100_iterations_partialeq"),
+        not_found(Strategy::PartialEq).named("find_not_found_partialeq"),
+        // Manual loop comparison
+        after_10(Strategy::Manual).named("find_after_10_iterations_manual"),
+        after_100(Strategy::Manual).named("find_after_100_iterations_manual"),
+        not_found(Strategy::Manual).named("find_not_found_manual"),
+        // Optimizations
+        after_10(Strategy::Unrolled).named("optimization_unrolled"),
+        after_10(Strategy::Simd).named("optimization_simd"),
+        after_10(Strategy::Branchless).named("optimization_branchless"),
+        after_10(Strategy::Unsafe).named("optimization_unsafe"),
+        not_found(Strategy::Unrolled).named("optimization_unrolled_not_found"),
+        after_100(Strategy::Simd).named("optimization_simd_100"),
+        not_found(Strategy::Simd).named("optimization_simd_1000_not_found"),
+        // P-Token inspired optimizations
+        after_10(Strategy::SolMemcmp).named("ptoken_sol_memcmp"),
+        after_10(Strategy::U128Cast).named("ptoken_u128_cast"),
+        after_10(Strategy::PointerEquality).named("ptoken_pointer_equality"),
+        after_10(Strategy::CombinedFast).named("ptoken_combined_fast"),
+        after_100(Strategy::U128Cast).named("ptoken_u128_cast_100"),
+        not_found(Strategy::U128Cast).named("ptoken_u128_cast_1000_not_found"),
+        // SIMD iteration variants
+        after_10(Strategy::SimdIterator).named("simd_iterator"),
+        after_10(Strategy::SimdZip).named("simd_zip"),
+        after_10(Strategy::SimdSlice).named("simd_slice"),
+        after_100(Strategy::SimdIterator).named("simd_iterator_100"),
+        not_found(Strategy::SimdIterator).named("simd_iterator_1000_not_found"),
+    ]
+}
+
+/// Scenarios of the `simd_integer_variants` bench, full scan per integer width.
+#[rustfmt::skip]
+pub fn simd_integer_scenarios() -> Vec<Scenario> {
+    [
+        ("simd_iterator_u16_1000_not_found", Strategy::SimdIteratorU16),
+        ("simd_iterator_u32_1000_not_found", Strategy::SimdIteratorU32),
+        ("simd_iterator_u64_1000_not_found", Strategy::SimdIterator),
+        ("simd_iterator_u128_1000_not_found", Strategy::SimdIteratorU128),
+    ]
+    .into_iter()
+    .map(|(name, strategy)| Scenario::new(1000, None, strategy).named(name))
+    .collect()
+}
+
+/// Scenarios of the `composite_lookup` bench, every `OwnerMintEntry` has the
+/// owner of the target key.
+pub fn composite_lookup_scenarios() -> Vec<Scenario> {
+    CompositeStrategy::ALL
+        .into_iter()
+        .flat_map(|strategy| [after_10(strategy), after_100(strategy), not_found(strategy)])
+        .collect()
+}
+
+/// Scenarios of the `entry_fast_path` bench: every strategy through
+/// `KeyValue::key` and through its `Entry` fast path.
+pub fn entry_fast_path_scenarios() -> Vec<Scenario> {
+    Strategy::ALL
+        .into_iter()
+        .flat_map(|strategy| [after_10(strategy), after_100(strategy), not_found(strategy)])
+        .flat_map(|scenario| [scenario.clone(), scenario.entry_fast_path()])
+        .collect()
+}
+
+/// Scenarios of the `key_ref` bench, each `simd_iterator` scenario is
+/// followed by the same `direct_field_access` scenario.
+pub fn key_ref_scenarios() -> Vec<Scenario> {
+    [after_10, after_100, not_found]
+        .into_iter()
+        .flat_map(|scenario| [Strategy::SimdIterator, Strategy::DirectFieldAccess].map(scenario))
+        .collect()
+}
+
+const KEY_DISTRIBUTION_STRATEGIES: [Strategy; 4] = [
+    Strategy::SimdIterator,
+    Strategy::U128Cast,
+    Strategy::SolMemcmp,
+    Strategy::PartialEq,
+];
+
+const SHARED_PREFIXES: [usize; 8] = [0, 4, 8, 12, 16, 20, 24, 31];
+
+/// Scenarios of the `key_distribution` bench: full scans of 100 entries whose
+/// keys share a prefix with the target key.
+pub fn key_distribution_scenarios() -> Vec<Scenario> {
+    KEY_DISTRIBUTION_STRATEGIES
+        .into_iter()
+        .flat_map(|strategy| {
+            SHARED_PREFIXES
+                .map(|n| Scenario::new(100, None, strategy).keys(KeyDistribution::SharedPrefix(n)))
+        })
+        .collect()
+}
+
+/// Scenarios of the `random_key_equal_case` bench: 100 single entry
+/// changelogs, the entry key is random and equal to the target key.
+pub fn random_key_equal_scenarios() -> Vec<Scenario> {
+    let seed = seed_config().seed;
+    (0..100)
+        .flat_map(|i| {
+            FAIL_EARLY_STRATEGIES.map(|(prefix, strategy)| {
+                Scenario::new(10, Some(0), strategy)
+                    .pushes(1)
+                    .num_iters(10)
+                    .seed(seed.wrapping_add(i))
+                    .named(format!("{prefix}_equal_random_{i:03}"))
+            })
+        })
+        .collect()
+}
+
+pub const DEPTH_SWEEP_CAPACITIES: [u64; 4] = [10, 100, 1000, 10_000];
+
+/// Hit depths measured per capacity, `DEPTH_SWEEP_POINTS` or 20.
+pub fn depth_sweep_points() -> u64 {
+    std::env::var("DEPTH_SWEEP_POINTS")
+        .map(|v| v.parse().expect("DEPTH_SWEEP_POINTS"))
+        .unwrap_or(20)
+}
+
+/// Evenly spaced depths from the newest to the oldest entry, then not found.
+pub fn depth_sweep_depths(capacity: u64, points: u64) -> Vec<Option<u64>> {
+    let points = points.clamp(2, capacity);
+    let mut depths: Vec<Option<u64>> = (0..points)
+        .map(|i| Some(i * (capacity - 1) / (points - 1)))
+        .collect();
+    depths.dedup();
+    depths.push(None);
+    depths
+}
+
+/// Scenarios of the `depth_sweep` bench, every strategy at every depth of
+/// every capacity.
+pub fn depth_sweep_scenarios(points: u64) -> Vec<Scenario> {
+    DEPTH_SWEEP_CAPACITIES
+        .into_iter()
+        .flat_map(|capacity| {
+            depth_sweep_depths(capacity, points)
+                .into_iter()
+                .flat_map(move |depth| {
+                    Strategy::ALL.map(|strategy| Scenario::new(capacity, depth, strategy))
+                })
+        })
+        .collect()
+}
+
+const FAIL_EARLY_STRATEGIES: [(&str, Strategy); 2] = [
+    ("simd", Strategy::SimdIterator),
+    ("partialeq", Strategy::PartialEq),
+];
+
+/// Single entry changelog, the entry key differs from the target key at
+/// `differ_at_position`.
+fn fail_early_case(
+    name: String,
+    strategy: Strategy,
+    differ_at_position: Option<usize>,
+) -> BenchCase {
+    let target_key = [9u8; 32];
+    let mut entry_key = target_key;
+    if let Some(pos) = differ_at_position {
+        entry_key[pos] = entry_key[pos].wrapping_add(1);
+    }
+    let account = changelog_account(10, [Entry::new(entry_key, TARGET_VALUE)]);
+    let expected = differ_at_position.is_none().then_some(TARGET_VALUE);
+    BenchCase::new(
+        name,
+        LookupInstruction::new(strategy, Some(10), target_key),
+        1,
+        expected,
+        account,
+    )
+}
+
+/// Cases of the `fail_early_position` bench: the equal case, then every
+/// position of the first differing byte.
+pub fn fail_early_cases() -> Vec<BenchCase> {
+    let mut cases = Vec::new();
+    for (prefix, strategy) in FAIL_EARLY_STRATEGIES {
+        cases.push(fail_early_case(
+            format!("{prefix}_equal_case"),
+            strategy,
+            None,
+        ));
+    }
+    for i in 0..32 {
+        for (prefix, strategy) in FAIL_EARLY_STRATEGIES {
+            let name = format!("{prefix}_fail_at_position_{i:02}");
+            cases.push(fail_early_case(name, strategy, Some(i)));
+        }
+    }
+    cases
+}
@@ -0,0 +1,112 @@
+//! Host benchmarks of (owner, mint) lookups: field by field with the mint or
+//! the owner first, and the whole 64 byte key.
+//!
+//! Every entry has the same owner, as in the changelog of one wallet, so
+//! owner first compares both fields of every entry.
+//!
+//! ```bash
+//! cargo bench --bench composite_key
+//! ```
+mod bench_support;
+
+use {
+    bench_support::changelog_data,
+    criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion},
+    optimize_cmp::changelog::{
+        simd_iterator_compare, CompositeKey, GenericChangelogRef, KeyValue, OwnerMint,
+        OwnerMintEntry,
+    },
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout},
+};
+
+const CAPACITY: u64 = 1000;
+
+/// `OwnerMint` compared owner first.
+#[derive(Copy, Clone, PartialEq, KnownLayout, Immutable, FromBytes, IntoBytes)]
+#[repr(C)]
+struct OwnerFirst(OwnerMint);
+
+impl CompositeKey for OwnerFirst {
+    const FIELDS: usize = 2;
+
+    #[inline(always)]
+    fn field(&self, index: usize) -> &[u8; 32] {
+        match index {
+            0 => &self.0.owner,
+            _ => &self.0.mint,
+        }
+    }
+}
+
+#[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes, KeyValue)]
+#[repr(C)]
+struct OwnerFirstEntry {
+    #[value]
+    value: u64,
+    #[key]
+    key: OwnerFirst,
+}
+
+fn bench_composite(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0xc0de);
+    let owner: [u8; 32] = rng.gen();
+    let entries: Vec<OwnerMintEntry> = (0..CAPACITY)
+        .map(|value| OwnerMintEntry {
+            value,
+            key: OwnerMint {
+                owner,
+                mint: rng.gen(),
+            },
+        })
+        .collect();
+    let owner_first: Vec<OwnerFirstEntry> = entries
+        .iter()
+        .map(|entry| OwnerFirstEntry {
+            value: entry.value,
+            key: OwnerFirst(entry.key),
+        })
+        .collect();
+    let data = changelog_data(CAPACITY, entries.iter().copied());
+    let owner_first_data = changelog_data(CAPACITY, owner_first);
+    let changelog = GenericChangelogRef::<OwnerMintEntry>::from_bytes(&data).unwrap();
+    let owner_first_changelog =
+        GenericChangelogRef::<OwnerFirstEntry>::from_bytes(&owner_first_data).unwrap();
+
+    let mut group = c.benchmark_group("composite");
+    for (case, key) in [
+        ("depth_99", entries[entries.len() - 100].key),
+        (
+            "not_found",
+            OwnerMint {
+                owner,
+                mint: rng.gen(),
+            },
+        ),
+    ] {
+        group.bench_function(BenchmarkId::new("mint_first", case), |bench| {
+            bench.iter(|| {
+                changelog.find_latest_composite(black_box(key), None, simd_iterator_compare)
+            })
+        });
+        group.bench_function(BenchmarkId::new("owner_first", case), |bench| {
+            bench.iter(|| {
+                owner_first_changelog.find_latest_composite(
+                    black_box(OwnerFirst(key)),
+                    None,
+                    simd_iterator_compare,
+                )
+            })
+        });
+        group.bench_function(BenchmarkId::new("whole_key_simd_iterator", case), |bench| {
+            bench.iter(|| changelog.find_latest_simd_iterator(black_box(key), None))
+        });
+        group.bench_function(BenchmarkId::new("whole_key_partialeq", case), |bench| {
+            bench.iter(|| changelog.find_latest::<false>(black_box(key), None))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_composite);
+criterion_main!(benches);
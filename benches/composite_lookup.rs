@@ -0,0 +1,15 @@
+//! `LOOKUP_COMPOSITE` in an `OwnerMintEntry` changelog where every entry has
+//! the owner of the target key: field by field with the mint first against
+//! whole 64 byte key comparisons.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo bench --bench composite_lookup
+//! ```
+mod bench_support;
+
+use bench_support::{composite_lookup_scenarios, run_scenarios};
+
+fn main() {
+    run_scenarios("composite_lookup", &composite_lookup_scenarios());
+}
@@ -0,0 +1,63 @@
+//! CU vs hit depth for every strategy, used to find crossover points.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo bench --bench depth_sweep
+//! DEPTH_SWEEP_POINTS=100 cargo bench --bench depth_sweep  # finer sweep
+//! cargo bench --bench depth_sweep -- --seeds 5              # median of 5 seeds
+//! ```
+//!
+//! Output: `target/benches/depth_sweep.csv`, `target/benches/depth_sweep.md`
+mod bench_support;
+
+use {
+    bench_support::{
+        depth_sweep_depths, depth_sweep_points, mollusk, Scenario, DEPTH_SWEEP_CAPACITIES,
+    },
+    optimize_cmp::strategy::Strategy,
+    std::fmt::Write,
+};
+
+fn main() {
+    solana_logger::setup_with("");
+    let points = depth_sweep_points();
+
+    let mollusk = mollusk();
+    let mut csv = String::from("capacity,depth,comparisons,strategy,cu\n");
+    let mut md = String::from("# Hit depth sweep\n");
+    for capacity in DEPTH_SWEEP_CAPACITIES {
+        writeln!(md, "\n## Capacity {capacity}\n").unwrap();
+        md.push_str("| Depth |");
+        for strategy in Strategy::ALL {
+            write!(md, " {} |", strategy.name()).unwrap();
+        }
+        md.push_str("\n|-------|");
+        md.push_str(&"-----|".repeat(Strategy::ALL.len()));
+        md.push('\n');
+
+        for depth in depth_sweep_depths(capacity, points) {
+            let label = depth.map_or("not found".to_string(), |d| d.to_string());
+            write!(md, "| {label} |").unwrap();
+            for strategy in Strategy::ALL {
+                let scenario = Scenario::new(capacity, depth, strategy);
+                let cu = scenario.median_cu(&mollusk);
+                writeln!(
+                    csv,
+                    "{capacity},{},{},{},{cu}",
+                    depth.map_or(String::new(), |d| d.to_string()),
+                    scenario.comparisons(),
+                    strategy.name()
+                )
+                .unwrap();
+                write!(md, " {cu} |").unwrap();
+            }
+            md.push('\n');
+        }
+        println!("capacity {capacity} done");
+    }
+
+    std::fs::create_dir_all("target/benches").unwrap();
+    std::fs::write("target/benches/depth_sweep.csv", csv).unwrap();
+    std::fs::write("target/benches/depth_sweep.md", &md).unwrap();
+    println!("{md}");
+}
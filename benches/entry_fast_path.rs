@@ -0,0 +1,14 @@
+//! Every strategy through `KeyValue::key` and through its `Entry` fast path,
+//! with the 10 and 100 iteration limits of `changelog_cu` and a full scan.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo bench --bench entry_fast_path
+//! ```
+mod bench_support;
+
+use bench_support::{entry_fast_path_scenarios, run_scenarios};
+
+fn main() {
+    run_scenarios("entry_fast_path", &entry_fast_path_scenarios());
+}
@@ -0,0 +1,11 @@
+//! Full scans of 100 entries whose keys share a prefix with the target key.
+//!
+//! Fail-early comparators pay for every shared u64 chunk, keys ground to
+//! match the first 24 bytes of a target are their worst case.
+mod bench_support;
+
+use bench_support::{key_distribution_scenarios, run_scenarios};
+
+fn main() {
+    run_scenarios("key_distribution", &key_distribution_scenarios());
+}
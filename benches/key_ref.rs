@@ -0,0 +1,43 @@
+//! The generic `simd_iterator` lookup, which compares through
+//! `KeyValue::key_ref`, against `direct_field_access`, which compares
+//! `entry.mint` in place. Both should cost the same.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo bench --bench key_ref
+//! cargo bench --bench key_ref -- --seeds 5  # median of 5 seeds
+//! ```
+//!
+//! Output: `target/benches/key_ref.md`
+mod bench_support;
+
+use {
+    bench_support::{key_ref_scenarios, mollusk},
+    std::fmt::Write,
+};
+
+fn main() {
+    solana_logger::setup_with("");
+    let mollusk = mollusk();
+
+    let mut report = String::from(
+        "| Scenario | simd_iterator | direct_field_access | Difference |\n|----------|---------------|---------------------|------------|\n",
+    );
+    let scenarios = key_ref_scenarios();
+    for (name, pair) in ["after_10", "after_100", "1000_not_found"]
+        .iter()
+        .zip(scenarios.chunks(2))
+    {
+        let [generic, direct] = [&pair[0], &pair[1]].map(|scenario| scenario.median_cu(&mollusk));
+        writeln!(
+            report,
+            "| {name} | {generic} | {direct} | {} |",
+            generic as i64 - direct as i64
+        )
+        .unwrap();
+    }
+
+    println!("{report}");
+    std::fs::create_dir_all("target/benches").unwrap();
+    std::fs::write("target/benches/key_ref.md", report).unwrap();
+}
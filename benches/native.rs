@@ -0,0 +1,64 @@
+//! Host micro-benchmarks of the comparators and lookup strategies.
+//!
+//! Fast local feedback without `cargo build-sbf`, confirm with the mollusk
+//! benches. `sol_memcmp` uses the host fallback instead of the syscall.
+//!
+//! ```bash
+//! cargo bench --bench native
+//! cargo bench --bench native -- compare/simd_iterator
+//! ```
+mod bench_support;
+
+use {
+    bench_support::Scenario,
+    criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion},
+    optimize_cmp::{
+        changelog::{Entry, GenericChangelogRef},
+        memory::AlignedBytes,
+        strategy::{Strategy, COMPARATORS},
+    },
+};
+
+fn bench_comparators(c: &mut Criterion) {
+    let a = [9u8; 32];
+    let mut differ_first = a;
+    differ_first[0] = 10;
+    let mut differ_last = a;
+    differ_last[31] = 10;
+
+    let mut group = c.benchmark_group("compare");
+    for (strategy, compare) in COMPARATORS {
+        for (case, b) in [
+            ("equal", a),
+            ("differ_first", differ_first),
+            ("differ_last", differ_last),
+        ] {
+            group.bench_with_input(BenchmarkId::new(strategy.name(), case), &b, |bench, b| {
+                bench.iter(|| compare(black_box(&a), black_box(b)))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_strategies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_latest");
+    for (case, hit_depth) in [("depth_99", Some(99)), ("not_found", None)] {
+        // Same changelog for every strategy
+        let scenario = Scenario::new(1000, hit_depth, Strategy::SimdIterator).build();
+        // Account data is a `Vec<u8>`, copy it to aligned memory like the
+        // runtime does
+        let data = AlignedBytes::from_slice(&scenario.accounts[0].1.data);
+        let changelog = GenericChangelogRef::<Entry>::from_bytes(&data).unwrap();
+        for strategy in Strategy::ALL {
+            let lookup = strategy.lookup_fn();
+            group.bench_function(BenchmarkId::new(strategy.name(), case), |bench| {
+                bench.iter(|| lookup(&changelog, black_box(scenario.key), None))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_comparators, bench_strategies);
+criterion_main!(benches);
@@ -0,0 +1,250 @@
+//! `#[derive(KeyValue)]` for changelog entries.
+//!
+//! ```ignore
+//! #[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes, KeyValue)]
+//! #[repr(C)]
+//! pub struct Entry {
+//!     #[value]
+//!     pub value: u64,
+//!     #[key]
+//!     pub mint: [u8; 32],
+//! }
+//! ```
+//!
+//! Implements `KeyValue` with `key_ref`/`value_ref` borrowing the fields, and
+//! `DirectFieldAccess` if the key is a `[u8; 32]`. Fails to compile if the
+//! struct is not `#[repr(C)]`, is more than 8 byte aligned or is not a zero
+//! copy type.
+use {
+    proc_macro::TokenStream,
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::quote,
+    syn::{
+        parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Error, Expr, Fields, Lit,
+        Member, Meta, Token, Type,
+    },
+};
+
+#[proc_macro_derive(KeyValue, attributes(key, value))]
+pub fn derive_key_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Field marked with `#[key]` or `#[value]`.
+struct MarkedField<'a> {
+    member: Member,
+    ty: &'a Type,
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    if !has_repr_c(input)? {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`#[derive(KeyValue)]` needs `#[repr(C)]`, the entry is read from account data",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`#[derive(KeyValue)]` only supports structs",
+            ))
+        }
+    };
+    let key = marked_field(input, fields, "key")?;
+    let value = marked_field(input, fields, "value")?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (key_member, key_ty) = (&key.member, key.ty);
+    let (value_member, value_ty) = (&value.member, value.ty);
+
+    let direct_field_access = is_pubkey(key_ty).then(|| {
+        quote! {
+            impl #impl_generics ::optimize_cmp::changelog::DirectFieldAccess
+                for #name #ty_generics #where_clause
+            {
+                #[inline(always)]
+                fn key_field(&self) -> &[u8; 32] {
+                    &self.#key_member
+                }
+            }
+        }
+    });
+
+    // Layout checks only work for concrete types
+    let layout_checks = input.generics.params.is_empty().then(|| {
+        let align_message = format!(
+            "`{name}` must be at most 8 byte aligned, entries start 8 bytes aligned after the ring header"
+        );
+        quote! {
+            const _: () = {
+                fn assert_zero_copy<T: ::optimize_cmp::__private::ZeroCopyTraits>() {}
+                let _ = assert_zero_copy::<#name>;
+                assert!(::core::mem::align_of::<#name>() <= 8, #align_message);
+            };
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::optimize_cmp::changelog::KeyValue for #name #ty_generics #where_clause {
+            type Key = #key_ty;
+            type Value = #value_ty;
+
+            #[inline(always)]
+            fn key_ref(&self) -> &#key_ty {
+                &self.#key_member
+            }
+
+            #[inline(always)]
+            fn value_ref(&self) -> &#value_ty {
+                &self.#value_member
+            }
+        }
+
+        #direct_field_access
+        #layout_checks
+    })
+}
+
+fn has_repr_c(input: &DeriveInput) -> Result<bool, Error> {
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        let reprs = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if reprs.iter().any(|repr| repr.path().is_ident("C")) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// The one field with the `#[attr]` attribute.
+fn marked_field<'a>(
+    input: &DeriveInput,
+    fields: &'a Fields,
+    attr: &str,
+) -> Result<MarkedField<'a>, Error> {
+    let mut marked = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|a| a.path().is_ident(attr)));
+    let Some((index, field)) = marked.next() else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            format!("`#[derive(KeyValue)]` needs a `#[{attr}]` field"),
+        ));
+    };
+    if let Some((_, duplicate)) = marked.next() {
+        return Err(Error::new_spanned(
+            duplicate,
+            format!("more than one `#[{attr}]` field"),
+        ));
+    }
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(syn::Index {
+            index: index as u32,
+            span: Span::call_site(),
+        }),
+    };
+    Ok(MarkedField {
+        member,
+        ty: &field.ty,
+    })
+}
+
+/// `[u8; 32]`, the key type of the direct field access lookups.
+fn is_pubkey(ty: &Type) -> bool {
+    let Type::Array(array) = ty else {
+        return false;
+    };
+    let is_u8 = matches!(&*array.elem, Type::Path(path) if path.path.is_ident("u8"));
+    let is_32 = matches!(
+        &array.len,
+        Expr::Lit(expr) if matches!(&expr.lit, Lit::Int(len) if len.base10_digits() == "32")
+    );
+    is_u8 && is_32
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, syn::parse_quote};
+
+    fn error(input: DeriveInput) -> String {
+        expand(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_expand() {
+        let input: DeriveInput = parse_quote! {
+            #[derive(Copy, Clone)]
+            #[repr(C, align(8))]
+            struct Entry {
+                #[value]
+                value: u64,
+                #[key]
+                mint: [u8; 32],
+            }
+        };
+        let expanded = expand(&input).unwrap().to_string();
+        assert!(expanded.contains("KeyValue for Entry"));
+        assert!(expanded.contains("DirectFieldAccess for Entry"));
+
+        let input: DeriveInput = parse_quote! {
+            #[repr(C)]
+            struct Address(#[key] [u8; 20], #[value] u32);
+        };
+        let expanded = expand(&input).unwrap().to_string();
+        assert!(expanded.contains("& self . 0"));
+        assert!(expanded.contains("& self . 1"));
+        assert!(!expanded.contains("DirectFieldAccess"));
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        assert!(error(parse_quote! {
+            struct Entry {
+                #[key]
+                mint: [u8; 32],
+                #[value]
+                value: u64,
+            }
+        })
+        .contains("#[repr(C)]"));
+        assert!(error(parse_quote! {
+            #[repr(C)]
+            struct Entry {
+                mint: [u8; 32],
+                #[value]
+                value: u64,
+            }
+        })
+        .contains("needs a `#[key]` field"));
+        assert!(error(parse_quote! {
+            #[repr(C)]
+            struct Entry {
+                #[key]
+                mint: [u8; 32],
+                #[key]
+                owner: [u8; 32],
+                #[value]
+                value: u64,
+            }
+        })
+        .contains("more than one `#[key]` field"));
+        assert!(error(parse_quote! {
+            #[repr(C)]
+            enum Entry {
+                A,
+            }
+        })
+        .contains("only supports structs"));
+    }
+}
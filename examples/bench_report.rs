@@ -0,0 +1,171 @@
+//! Runs the mollusk bench sets and writes `report.md`, `report.csv` and
+//! `report.json` with the CU cost per comparison.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo run --example bench_report              # target/benches
+//! cargo run --example bench_report -- bench     # regenerate the checked-in docs
+//! ```
+//!
+//! The base cost of a strategy is a lookup with `num_iters = 0`, it is
+//! subtracted before dividing by the number of compared entries.
+#[path = "../benches/bench_support/mod.rs"]
+mod bench_support;
+
+use {
+    bench_support::{
+        changelog_cu_scenarios, composite_lookup_scenarios, depth_sweep_points,
+        depth_sweep_scenarios, entry_fast_path_scenarios, fail_early_cases,
+        key_distribution_scenarios, key_ref_scenarios, mollusk, random_key_equal_scenarios,
+        simd_integer_scenarios, BenchCase, Lookup, Scenario,
+    },
+    mollusk_svm::Mollusk,
+    std::{collections::BTreeMap, fmt::Write},
+};
+
+struct Row {
+    set: &'static str,
+    name: String,
+    lookup: Lookup,
+    comparisons: u64,
+    cu: u64,
+    base_cu: u64,
+}
+
+impl Row {
+    fn cu_per_comparison(&self) -> Option<f64> {
+        (self.comparisons > 0)
+            .then(|| self.cu.saturating_sub(self.base_cu) as f64 / self.comparisons as f64)
+    }
+}
+
+/// Program cost of `lookup` without comparing any entry.
+fn base_cu(mollusk: &Mollusk, lookup: Lookup) -> u64 {
+    let case = Scenario::new(10, None, lookup)
+        .pushes(1)
+        .num_iters(0)
+        .build();
+    case.run(mollusk)
+}
+
+fn markdown(rows: &[Row]) -> String {
+    let mut md =
+        String::from("# Benchmark report\n\nGenerated by `cargo run --example bench_report`.\n");
+    let mut set = "";
+    for row in rows {
+        if row.set != set {
+            set = row.set;
+            writeln!(md, "\n## {set}\n").unwrap();
+            md.push_str("| Name | Strategy | Comparisons | CUs | Base CUs | CU/comparison |\n");
+            md.push_str("|------|----------|-------------|-----|----------|---------------|\n");
+        }
+        let per_comparison = row
+            .cu_per_comparison()
+            .map_or("-".to_string(), |v| format!("{v:.1}"));
+        writeln!(
+            md,
+            "| {} | {} | {} | {} | {} | {per_comparison} |",
+            row.name,
+            row.lookup.name(),
+            row.comparisons,
+            row.cu,
+            row.base_cu
+        )
+        .unwrap();
+    }
+    md
+}
+
+fn csv(rows: &[Row]) -> String {
+    let mut csv = String::from("set,name,strategy,comparisons,cu,base_cu,cu_per_comparison\n");
+    for row in rows {
+        let per_comparison = row
+            .cu_per_comparison()
+            .map_or(String::new(), |v| format!("{v:.2}"));
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{per_comparison}",
+            row.set,
+            row.name,
+            row.lookup.name(),
+            row.comparisons,
+            row.cu,
+            row.base_cu
+        )
+        .unwrap();
+    }
+    csv
+}
+
+fn json(rows: &[Row]) -> String {
+    let objects: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let per_comparison = row
+                .cu_per_comparison()
+                .map_or("null".to_string(), |v| format!("{v:.2}"));
+            format!(
+                "  {{\"set\": \"{}\", \"name\": \"{}\", \"strategy\": \"{}\", \"comparisons\": {}, \
+                 \"cu\": {}, \"base_cu\": {}, \"cu_per_comparison\": {per_comparison}}}",
+                row.set,
+                row.name,
+                row.lookup.name(),
+                row.comparisons,
+                row.cu,
+                row.base_cu
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+fn main() {
+    solana_logger::setup_with("");
+    let out_dir = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "target/benches".to_string());
+
+    let build = |scenarios: Vec<Scenario>| scenarios.iter().map(Scenario::build).collect();
+    let sets: [(&str, Vec<BenchCase>); 9] = [
+        ("changelog_cu", build(changelog_cu_scenarios())),
+        ("simd_integer_variants", build(simd_integer_scenarios())),
+        ("fail_early_position", fail_early_cases()),
+        ("random_key_equal_case", build(random_key_equal_scenarios())),
+        ("key_distribution", build(key_distribution_scenarios())),
+        (
+            "depth_sweep",
+            build(depth_sweep_scenarios(depth_sweep_points())),
+        ),
+        ("entry_fast_path", build(entry_fast_path_scenarios())),
+        ("key_ref", build(key_ref_scenarios())),
+        ("composite_lookup", build(composite_lookup_scenarios())),
+    ];
+
+    let mollusk = mollusk();
+    let mut base = BTreeMap::new();
+    let mut rows = Vec::new();
+    for (set, cases) in &sets {
+        for case in cases {
+            let base_cu = *base
+                .entry(case.lookup.name())
+                .or_insert_with(|| base_cu(&mollusk, case.lookup));
+            rows.push(Row {
+                set,
+                name: case.name.clone(),
+                lookup: case.lookup,
+                comparisons: case.comparisons,
+                cu: case.run(&mollusk),
+                base_cu,
+            });
+        }
+    }
+
+    std::fs::create_dir_all(&out_dir).unwrap();
+    std::fs::write(format!("{out_dir}/report.md"), markdown(&rows)).unwrap();
+    std::fs::write(format!("{out_dir}/report.csv"), csv(&rows)).unwrap();
+    std::fs::write(format!("{out_dir}/report.json"), json(&rows)).unwrap();
+    println!(
+        "Wrote {} rows to {out_dir}/report.{{md,csv,json}}",
+        rows.len()
+    );
+}
@@ -1,93 +1,152 @@
+//! Runs the CU tracking lookup and turns its checkpoint logs into a CU per
+//! entry and CU per chunk curve.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo run --example cu_tracking
+//! ```
+//!
+//! Output: `target/benches/cu_curve_entries.csv`, `target/benches/cu_curve_chunks.csv`
+#[path = "../benches/bench_support/mod.rs"]
+mod bench_support;
+
 use {
-    light_zero_copy::cyclic_vec::ZeroCopyCyclicVecU64,
-    mollusk_svm::Mollusk,
-    optimize_cmp::changelog::{Entry, GenericChangelog},
-    rand::{Rng, SeedableRng},
-    rand::rngs::StdRng,
-    solana_account::Account,
-    solana_program::{
-        instruction::{AccountMeta, Instruction},
-        pubkey::Pubkey,
+    bench_support::{mollusk, Scenario, PROGRAM_ID},
+    optimize_cmp::{
+        changelog::{CuCheckpoint, CuCheckpoints},
+        instruction::{unpack_lookup_result, CuTrackingInstruction, CU_CHECKPOINT_TAG},
+        strategy::Strategy,
     },
+    solana_program::instruction::{AccountMeta, Instruction},
+    std::{fmt::Write, sync::Mutex},
 };
 
-const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
-    0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0x10, 0x11, 0x12,
-    0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20,
-]);
+/// Collects program logs, mollusk emits them as `log::debug!` records.
+struct LogCapture;
 
-const SIMD_ITERATOR_CU_TRACKING: u8 = 39;
-const BENCHMARK_SEED: u64 = 9876543210987654321;
+static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
 
-fn create_random_mint(rng: &mut StdRng) -> [u8; 32] {
-    let mut bytes = [0u8; 32];
-    rng.fill(&mut bytes);
-    bytes
-}
+impl log::Log for LogCapture {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
 
-fn main() {
-    // Enable verbose logging to see CU tracking
-    solana_logger::setup_with("debug");
+    fn log(&self, record: &log::Record) {
+        LOGS.lock().unwrap().push(record.args().to_string());
+    }
 
-    let mollusk = Mollusk::new(&PROGRAM_ID, "./target/deploy/optimize_cmp");
+    fn flush(&self) {}
+}
 
-    // Create a small changelog with 100 entries
-    let capacity = 100u64;
-    let mut backing_store =
-        vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
-    let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+/// Pairs every checkpoint log with the remaining compute units logged after it.
+fn parse_checkpoints(logs: &[String]) -> Vec<(CuCheckpoint, u64)> {
+    let tag = format!("Program log: {CU_CHECKPOINT_TAG:#x}, ");
+    let mut checkpoints = Vec::new();
+    let mut pending = None;
+    for line in logs {
+        if let Some(args) = line.strip_prefix(&tag) {
+            let args: Vec<usize> = args
+                .split(", ")
+                .map(|v| usize::from_str_radix(v.trim_start_matches("0x"), 16).unwrap())
+                .collect();
+            pending = Some(CuCheckpoint {
+                entries: args[0],
+                chunks: args[1],
+            });
+        } else if let Some(remaining) = line
+            .strip_prefix("Program consumption: ")
+            .and_then(|l| l.strip_suffix(" units remaining"))
+        {
+            if let Some(checkpoint) = pending.take() {
+                checkpoints.push((checkpoint, remaining.parse().unwrap()));
+            }
+        }
+    }
+    checkpoints
+}
 
-    let mut rng = StdRng::seed_from_u64(BENCHMARK_SEED);
+/// Runs one CU tracking lookup and returns `(checkpoint, CU since the previous
+/// checkpoint)` with the cost of the checkpoint itself subtracted.
+fn run(scenario: Scenario, checkpoints: CuCheckpoints) -> Vec<(CuCheckpoint, u64)> {
+    let case = scenario.build();
+    let changelog_pubkey = case.accounts[0].0;
+    let instruction = Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &CuTrackingInstruction {
+            checkpoints,
+            num_iters: scenario.num_iters,
+            key: case.key,
+        }
+        .pack(),
+        vec![AccountMeta::new_readonly(changelog_pubkey, false)],
+    );
 
-    // Create a target key that will be found after 10 iterations
-    let target_key_10 = create_random_mint(&mut rng);
+    LOGS.lock().unwrap().clear();
+    let result = mollusk().process_instruction(&instruction, &case.accounts);
+    assert!(result.raw_result.is_ok(), "{:?}", result.raw_result);
+    assert_eq!(unpack_lookup_result(&result.return_data), Ok(case.expected));
+    let logged = parse_checkpoints(&LOGS.lock().unwrap());
 
-    // Fill with some random entries
-    for _i in 0..90 {
-        let mint = create_random_mint(&mut rng);
-        let value = rng.gen::<u64>();
-        changelog.push(Entry::new(mint, value));
-    }
+    // The first two checkpoints are back to back
+    let overhead = logged[0].1 - logged[1].1;
+    logged
+        .windows(2)
+        .skip(1)
+        .map(|w| (w[1].0, (w[0].1 - w[1].1).saturating_sub(overhead)))
+        .collect()
+}
 
-    // Add 10 more entries, with our target at the end
-    for _ in 0..9 {
-        let mint = create_random_mint(&mut rng);
-        let value = rng.gen::<u64>();
-        changelog.push(Entry::new(mint, value));
+fn write_curve(path: &str, curve: &[(CuCheckpoint, u64)]) {
+    let mut csv = String::from("entries,chunks,cu,cumulative_cu\n");
+    let mut cumulative = 0;
+    for (checkpoint, cu) in curve {
+        cumulative += cu;
+        writeln!(
+            csv,
+            "{},{},{cu},{cumulative}",
+            checkpoint.entries, checkpoint.chunks
+        )
+        .unwrap();
     }
-    changelog.push(Entry::new(target_key_10, 12345));
+    std::fs::write(path, csv).unwrap();
+    println!("Wrote {path}");
+}
 
-    // Create instruction data
-    let mut instruction_data = vec![SIMD_ITERATOR_CU_TRACKING];
-    instruction_data.extend_from_slice(&target_key_10);
+fn main() {
+    log::set_logger(&LogCapture).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+    std::fs::create_dir_all("target/benches").unwrap();
 
-    let instruction = Instruction::new_with_bytes(
-        PROGRAM_ID,
-        &instruction_data,
-        vec![AccountMeta::new(Pubkey::new_unique(), false)],
+    // Full scan of 100 entries, one checkpoint per entry
+    let entries = run(
+        Scenario::new(100, None, Strategy::SimdIterator),
+        CuCheckpoints {
+            every_entries: 1,
+            per_chunk: false,
+        },
     );
+    let total: u64 = entries.iter().map(|(_, cu)| cu).sum();
+    println!(
+        "=== {} entries, {:.1} CU per entry ===",
+        entries.len(),
+        total as f64 / entries.len() as f64
+    );
+    write_curve("target/benches/cu_curve_entries.csv", &entries);
 
-    let account = Account {
-        lamports: 0,
-        data: backing_store,
-        owner: PROGRAM_ID,
-        executable: false,
-        rent_epoch: 0,
-    };
-
-    let accounts = vec![(Pubkey::new_unique(), account)];
-
-    println!("=== Running SIMD Iterator with detailed CU tracking ===");
-    
-    let result = mollusk.process_instruction(&instruction, &accounts);
-    
-    match result.raw_result {
-        Ok(()) => {
-            println!("Program executed successfully");
-            println!("Total CU consumed: {}", result.compute_units_consumed);
-        }
-        Err(e) => {
-            println!("Program execution failed: {:?}", e);
-        }
+    // Target is the 10th newest entry, one checkpoint per u64 chunk
+    let chunks = run(
+        Scenario::new(100, Some(9), Strategy::SimdIterator).num_iters(10),
+        CuCheckpoints {
+            every_entries: 0,
+            per_chunk: true,
+        },
+    );
+    println!("=== Per chunk ===");
+    for (checkpoint, cu) in &chunks {
+        println!(
+            "entry {:3} chunk {}: {cu} CU",
+            checkpoint.entries, checkpoint.chunks
+        );
     }
-}
\ No newline at end of file
+    write_curve("target/benches/cu_curve_chunks.csv", &chunks);
+}
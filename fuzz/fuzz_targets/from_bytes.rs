@@ -0,0 +1,13 @@
+//! Arbitrary account data through `from_bytes` and every lookup.
+#![no_main]
+
+use {
+    libfuzzer_sys::fuzz_target,
+    optimize_cmp::changelog::CuCheckpoints,
+    optimize_cmp_fuzz::{check_changelog, LookupKey},
+};
+
+fuzz_target!(|data: &[u8]| {
+    check_changelog(data, LookupKey::Stored(0), None, CuCheckpoints::default());
+    check_changelog(data, LookupKey::Raw([0; 32]), Some(1), CuCheckpoints::default());
+});
@@ -0,0 +1,58 @@
+//! Lookups with arbitrary keys and `num_iters` on small, mostly valid rings.
+//!
+//! Input: `[flags, num_iters: u32 LE, key_index, every_entries, key: [u8; 32],
+//! current_index, length, capacity, entries..]`. Header fields are single
+//! bytes so most inputs pass `from_bytes`, entries are zero padded to
+//! `capacity` unless `TRUNCATE` is set.
+#![no_main]
+
+use {
+    libfuzzer_sys::fuzz_target,
+    optimize_cmp::changelog::{CuCheckpoints, Entry},
+    optimize_cmp_fuzz::{check_changelog, LookupKey},
+};
+
+const HAS_NUM_ITERS: u8 = 1;
+const STORED_KEY: u8 = 1 << 1;
+const PER_CHUNK: u8 = 1 << 2;
+const TRUNCATE: u8 = 1 << 3;
+
+const INPUT_HEADER_LEN: usize = 42;
+const ACCOUNT_HEADER_LEN: usize = 24;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((input, entries)) = data.split_first_chunk::<INPUT_HEADER_LEN>() else {
+        return;
+    };
+    let flags = input[0];
+    let num_iters = u32::from_le_bytes(input[1..5].try_into().unwrap()) as usize;
+    let key = if flags & STORED_KEY != 0 {
+        LookupKey::Stored(input[5] as usize)
+    } else {
+        LookupKey::Raw(input[7..39].try_into().unwrap())
+    };
+    let checkpoints = CuCheckpoints {
+        every_entries: input[6] as u32,
+        per_chunk: flags & PER_CHUNK != 0,
+    };
+    let [current_index, length, capacity] = [input[39], input[40], input[41]].map(u64::from);
+
+    let mut account = Vec::with_capacity(ACCOUNT_HEADER_LEN + entries.len());
+    for field in [current_index, length, capacity] {
+        account.extend_from_slice(&field.to_le_bytes());
+    }
+    account.extend_from_slice(entries);
+    if flags & TRUNCATE == 0 {
+        let size = ACCOUNT_HEADER_LEN + capacity as usize * core::mem::size_of::<Entry>();
+        if account.len() < size {
+            account.resize(size, 0);
+        }
+    }
+
+    check_changelog(
+        &account,
+        key,
+        (flags & HAS_NUM_ITERS != 0).then_some(num_iters),
+        checkpoints,
+    );
+});
@@ -0,0 +1,92 @@
+//! Checks shared by the fuzz targets.
+use optimize_cmp::{
+    changelog::{CuCheckpoint, CuCheckpoints, Entry, GenericChangelog, GenericChangelogRef},
+    memory::AlignedBytes,
+    strategy::Strategy,
+};
+
+/// Key to look up.
+#[derive(Debug, Clone, Copy)]
+pub enum LookupKey {
+    /// Arbitrary key, usually not in the changelog.
+    Raw([u8; 32]),
+    /// Key of the entry at this storage index modulo the length.
+    Stored(usize),
+}
+
+/// Deserializes `account` with both `from_bytes` and runs every lookup.
+///
+/// Panics if the two deserializers disagree, if a lookup panics or reads out
+/// of bounds, or if a strategy disagrees with the generic `find_latest`.
+pub fn check_changelog(
+    account: &[u8],
+    key: LookupKey,
+    num_iters: Option<usize>,
+    checkpoints: CuCheckpoints,
+) {
+    // Copied into 8 byte aligned buffers, as on chain
+    let account = AlignedBytes::from_slice(account);
+    let mut account_mut = account.clone();
+    let changelog_ref = GenericChangelogRef::<Entry>::from_bytes(&account);
+    let changelog = GenericChangelog::<Entry>::from_bytes(&mut account_mut);
+    let (changelog_ref, changelog) = match (changelog_ref, changelog) {
+        (Ok(changelog_ref), Ok(changelog)) => (changelog_ref, changelog),
+        (changelog_ref, changelog) => {
+            assert_eq!(
+                changelog_ref.is_ok(),
+                changelog.is_ok(),
+                "from_bytes disagree: {:?} {:?}",
+                changelog_ref.err(),
+                changelog.err()
+            );
+            return;
+        }
+    };
+
+    let view = changelog.view();
+    assert!(changelog_ref.len() <= changelog_ref.capacity());
+    assert_eq!(view.len(), changelog_ref.len());
+    assert_eq!(view.capacity(), changelog_ref.capacity());
+
+    let key = match key {
+        LookupKey::Raw(key) => key,
+        LookupKey::Stored(index) if !changelog_ref.is_empty() => {
+            changelog_ref.entries[index % changelog_ref.len()].mint
+        }
+        LookupKey::Stored(_) => [0; 32],
+    };
+
+    let expected = changelog_ref.find_latest::<false>(key, num_iters);
+
+    for strategy in Strategy::ALL {
+        let lookup = strategy.lookup_fn();
+        let result = lookup(&changelog_ref, key, num_iters);
+        assert_eq!(
+            lookup(&view, key, num_iters),
+            result,
+            "{} on GenericChangelog",
+            strategy.name()
+        );
+        assert_eq!(result, expected, "{}", strategy.name());
+        assert_eq!(
+            strategy.entry_lookup_fn()(&changelog_ref, key, num_iters),
+            expected,
+            "{} entry fast path",
+            strategy.name()
+        );
+    }
+
+    let mut last = CuCheckpoint::entry(0);
+    let result = changelog_ref.find_latest_simd_iterator_with_cu_tracking(
+        key,
+        num_iters,
+        checkpoints,
+        |checkpoint| {
+            assert!(checkpoint.entries >= last.entries);
+            assert!(checkpoint.entries <= changelog_ref.len());
+            assert!(checkpoint.chunks <= 4);
+            last = checkpoint;
+        },
+    );
+    assert_eq!(result, expected, "cu_tracking");
+}
@@ -0,0 +1,9 @@
+//! Program entrypoints, only one adapter can be enabled at a time.
+
+#[cfg(all(feature = "solana-program", feature = "pinocchio"))]
+compile_error!("features `solana-program` and `pinocchio` both define an entrypoint");
+
+#[cfg(feature = "pinocchio")]
+pub mod pinocchio;
+#[cfg(feature = "solana-program")]
+pub mod solana;
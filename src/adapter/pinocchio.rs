@@ -0,0 +1,47 @@
+//! `pinocchio` entrypoint, lookups only.
+use ::pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError,
+    pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    error::ChangelogError,
+    instruction::{pack_lookup_result, LOOKUP, LOOKUP_ENTRY},
+    processor::process_lookup,
+};
+
+#[cfg(feature = "std")]
+::pinocchio::entrypoint!(process_instruction);
+
+// Without `std` the crate is `no_std`, `entrypoint!` has no `#[panic_handler]`.
+#[cfg(not(feature = "std"))]
+::pinocchio::program_entrypoint!(process_instruction);
+#[cfg(not(feature = "std"))]
+::pinocchio::default_allocator!();
+#[cfg(not(feature = "std"))]
+::pinocchio::nostd_panic_handler!();
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.first() {
+        Some(&(LOOKUP | LOOKUP_ENTRY)) => {
+            let [changelog_account, ..] = accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let data = changelog_account.try_borrow_data()?;
+            let result = process_lookup(instruction_data, &data).map_err(log_error)?;
+            set_return_data(&pack_lookup_result(result));
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+#[cold]
+fn log_error(e: ChangelogError) -> ProgramError {
+    ::pinocchio::log::sol_log(e.as_str());
+    e.into()
+}
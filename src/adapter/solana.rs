@@ -0,0 +1,150 @@
+//! `solana-program` entrypoint.
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{entrypoint, ProgramResult},
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[cfg(feature = "bench-variants")]
+use crate::comparisons::{
+    benchmark_default_comparison, benchmark_manual_loop, benchmark_unrolled_comparison,
+    benchmark_unsafe_pointer,
+};
+#[cfg(feature = "bench-variants")]
+use crate::{
+    changelog::CuCheckpoint,
+    instruction::{CU_CHECKPOINT_TAG, LOOKUP_COMPOSITE, LOOKUP_CU_TRACKING},
+    processor::{process_composite_lookup, process_lookup_with_cu_tracking},
+};
+use crate::{
+    error::ChangelogError,
+    instruction::{pack_lookup_result, LOOKUP, LOOKUP_ENTRY},
+    processor::process_lookup,
+};
+
+entrypoint!(process_instruction);
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if instruction_data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    match instruction_data[0] {
+        // Comparison benchmarks (1-4)
+        #[cfg(feature = "bench-variants")]
+        1..=4 => {
+            // Test data - 32-byte arrays
+            let array1: [u8; 32] = [
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+                24, 25, 26, 27, 28, 29, 30, 31, 32,
+            ];
+
+            let array2: [u8; 32] = [
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+                24, 25, 26, 27, 28, 29, 30, 31, 33, // Different last byte
+            ];
+
+            let array3: [u8; 32] = [
+                2, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, // Different first byte
+                17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+            ];
+
+            match instruction_data[0] {
+                1 => benchmark_default_comparison(&array1, &array2, &array3),
+                2 => benchmark_manual_loop(&array1, &array2, &array3),
+                3 => benchmark_unrolled_comparison(&array1, &array2, &array3),
+                4 => benchmark_unsafe_pointer(&array1, &array2, &array3),
+                _ => unreachable!(),
+            }
+        }
+
+        // Reference vs value comparison test
+        #[cfg(feature = "bench-variants")]
+        33 => {
+            let array1: [u8; 32] = [1; 32];
+            let array2: [u8; 32] = [1; 32];
+            let array3: [u8; 32] = [2; 32];
+
+            // Test various comparison methods
+            let ref1 = &array1;
+            let ref2 = &array2;
+            let ref3 = &array3;
+
+            // Reference comparison
+            let _result1 = ref1 == ref2; // Same content, different memory locations
+            let _result2 = ref1 == ref3; // Different content
+            let _result3 = ref1 == &array1; // Same content, same memory location
+
+            // Pointer comparison for reference
+            let _result4 = core::ptr::eq(ref1, ref2);
+            let _result5 = core::ptr::eq(ref1, &array1);
+        }
+
+        // Changelog lookup with any strategy and iteration limit
+        LOOKUP | LOOKUP_ENTRY => {
+            if accounts.is_empty() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            // Read-only borrow, the account doesn't need to be writable for lookups
+            let data = accounts[0].data.borrow();
+            let result = process_lookup(instruction_data, &data).map_err(log_error)?;
+            set_return_data(&pack_lookup_result(result));
+        }
+
+        // simd_iterator lookup logging remaining compute units at checkpoints
+        #[cfg(feature = "bench-variants")]
+        LOOKUP_CU_TRACKING => {
+            if accounts.is_empty() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            let data = accounts[0].data.borrow();
+            let result =
+                process_lookup_with_cu_tracking(instruction_data, &data, log_cu_checkpoint)
+                    .map_err(log_error)?;
+            set_return_data(&pack_lookup_result(result));
+        }
+
+        // (owner, mint) lookup in an `OwnerMintEntry` changelog
+        #[cfg(feature = "bench-variants")]
+        LOOKUP_COMPOSITE => {
+            if accounts.is_empty() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            let data = accounts[0].data.borrow();
+            let result = process_composite_lookup(instruction_data, &data).map_err(log_error)?;
+            set_return_data(&pack_lookup_result(result));
+        }
+
+        _ => {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    Ok(())
+}
+
+/// Logs the checkpoint position followed by the remaining compute units.
+#[cfg(feature = "bench-variants")]
+#[inline(never)]
+fn log_cu_checkpoint(checkpoint: CuCheckpoint) {
+    solana_program::log::sol_log_64(
+        CU_CHECKPOINT_TAG,
+        checkpoint.entries as u64,
+        checkpoint.chunks as u64,
+        0,
+        0,
+    );
+    solana_program::log::sol_log_compute_units();
+}
+
+#[cold]
+fn log_error(e: ChangelogError) -> ProgramError {
+    e.print();
+    e.into()
+}
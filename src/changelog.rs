@@ -1,18 +1,20 @@
 use light_zero_copy::cyclic_vec::ZeroCopyCyclicVecU64;
 use light_zero_copy::ZeroCopyTraits;
-use solana_program::pubkey::Pubkey;
-use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zerocopy::{FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout};
+
+use crate::error::ChangelogError;
 
 // Trait for manual comparison
 pub trait ManualCompare: Copy {
     fn manual_compare(&self, other: &Self) -> bool;
 }
 
-impl ManualCompare for [u8; 32] {
+impl<K: IntoBytes + Immutable + Copy> ManualCompare for K {
     #[inline(always)]
     fn manual_compare(&self, other: &Self) -> bool {
-        for i in 0..32 {
-            if self[i] != other[i] {
+        let (a, b) = (self.as_bytes(), other.as_bytes());
+        for i in 0..a.len() {
+            if a[i] != b[i] {
                 return false;
             }
         }
@@ -57,12 +59,33 @@ pub fn unrolled_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
         && a[31] == b[31]
 }
 
+/// Loads a `[u8; 32]` key as `N` chunks of `C`.
+///
+/// Aligned load if the key is aligned for `C`, like entry keys in 8 byte
+/// aligned account data, `read_unaligned` otherwise, e.g. for a key copied
+/// out of instruction data. SBF may split unaligned loads into byte loads.
+#[inline(always)]
+pub fn load_chunks<C: FromBytes, const N: usize>(key: &[u8; 32]) -> [C; N] {
+    const { assert!(core::mem::size_of::<[C; N]>() == 32, "chunks must cover the key") };
+    let chunks = key.as_ptr() as *const [C; N];
+    // SAFETY: 32 readable bytes, every bit pattern is a valid `C` (`FromBytes`).
+    unsafe {
+        if chunks.is_aligned() {
+            chunks.read()
+        } else {
+            chunks.read_unaligned()
+        }
+    }
+}
+
 // Optimization 2: SIMD-style u64 chunk comparison
 #[inline(always)]
 pub fn simd_style_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let a_chunks = unsafe { std::slice::from_raw_parts(a.as_ptr() as *const u64, 4) };
-    let b_chunks = unsafe { std::slice::from_raw_parts(b.as_ptr() as *const u64, 4) };
+    simd_style_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
 
+#[inline(always)]
+pub fn simd_style_compare_chunks(a_chunks: &[u64; 4], b_chunks: &[u64; 4]) -> bool {
     a_chunks[0] == b_chunks[0]
         && a_chunks[1] == b_chunks[1]
         && a_chunks[2] == b_chunks[2]
@@ -100,25 +123,26 @@ pub fn unsafe_fast_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
 // Optimization 5: sol_memcmp (Solana's optimized memory compare)
 #[inline(always)]
 pub fn sol_memcmp_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    use solana_program::program_memory::sol_memcmp;
+    use crate::memory::sol_memcmp;
     sol_memcmp(a, b, 32) == 0
 }
 
 // Optimization 6: u128 casting for bulk comparison (inspired by p-token patterns)
 #[inline(always)]
 pub fn u128_cast_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    unsafe {
-        let a_u128 = std::slice::from_raw_parts(a.as_ptr() as *const u128, 2);
-        let b_u128 = std::slice::from_raw_parts(b.as_ptr() as *const u128, 2);
-        a_u128[0] == b_u128[0] && a_u128[1] == b_u128[1]
-    }
+    u128_cast_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
+
+#[inline(always)]
+pub fn u128_cast_compare_chunks(a_u128: &[u128; 2], b_u128: &[u128; 2]) -> bool {
+    a_u128[0] == b_u128[0] && a_u128[1] == b_u128[1]
 }
 
 // Optimization 7: Pointer equality fast path (p-token pattern)
 #[inline(always)]
 pub fn pointer_equality_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
     // Fast path: check if same memory location
-    if std::ptr::eq(a, b) {
+    if core::ptr::eq(a, b) {
         return true;
     }
     // Fallback to content comparison
@@ -129,20 +153,24 @@ pub fn pointer_equality_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
 #[inline(always)]
 pub fn combined_fast_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
     // Fast path: pointer equality
-    if std::ptr::eq(a, b) {
+    if core::ptr::eq(a, b) {
         return true;
     }
     // Use Solana's optimized memcmp
-    use solana_program::program_memory::sol_memcmp;
+    use crate::memory::sol_memcmp;
     sol_memcmp(a, b, 32) == 0
 }
 
 // Optimization 9: SIMD-style with iteration (your suggestion)
 #[inline(always)]
 pub fn simd_iterator_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let a_chunks = unsafe { std::slice::from_raw_parts(a.as_ptr() as *const u64, 4) };
-    let b_chunks = unsafe { std::slice::from_raw_parts(b.as_ptr() as *const u64, 4) };
+    simd_iterator_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
 
+/// `simd_iterator_compare` on keys loaded with `load_chunks`, lookups load
+/// the search key once instead of once per entry.
+#[inline(always)]
+pub fn simd_iterator_compare_chunks(a_chunks: &[u64; 4], b_chunks: &[u64; 4]) -> bool {
     // Iterate over chunks with early exit
     for i in 0..4 {
         if a_chunks[i] != b_chunks[i] {
@@ -152,12 +180,41 @@ pub fn simd_iterator_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
     true
 }
 
+/// `simd_iterator_compare` that calls `checkpoint(chunks)` after every
+/// compared u64 chunk, used to attribute CU to single chunks.
+#[inline(always)]
+pub fn simd_iterator_compare_with_checkpoints(
+    a: &[u8; 32],
+    b: &[u8; 32],
+    checkpoint: impl FnMut(usize),
+) -> bool {
+    simd_iterator_compare_chunks_with_checkpoints(&load_chunks(a), &load_chunks(b), checkpoint)
+}
+
+#[inline(always)]
+pub fn simd_iterator_compare_chunks_with_checkpoints(
+    a_chunks: &[u64; 4],
+    b_chunks: &[u64; 4],
+    mut checkpoint: impl FnMut(usize),
+) -> bool {
+    for i in 0..4 {
+        let equal = a_chunks[i] == b_chunks[i];
+        checkpoint(i + 1);
+        if !equal {
+            return false;
+        }
+    }
+    true
+}
+
 // Optimization 10: SIMD-style with zip iterator (more idiomatic)
 #[inline(always)]
 pub fn simd_zip_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let a_chunks = unsafe { std::slice::from_raw_parts(a.as_ptr() as *const u64, 4) };
-    let b_chunks = unsafe { std::slice::from_raw_parts(b.as_ptr() as *const u64, 4) };
+    simd_zip_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
 
+#[inline(always)]
+pub fn simd_zip_compare_chunks(a_chunks: &[u64; 4], b_chunks: &[u64; 4]) -> bool {
     // Use iterator with all() for early exit
     a_chunks.iter().zip(b_chunks.iter()).all(|(a, b)| a == b)
 }
@@ -165,9 +222,11 @@ pub fn simd_zip_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
 // Optimization 11: SIMD with slice comparison (let Rust optimize)
 #[inline(always)]
 pub fn simd_slice_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let a_chunks = unsafe { std::slice::from_raw_parts(a.as_ptr() as *const u64, 4) };
-    let b_chunks = unsafe { std::slice::from_raw_parts(b.as_ptr() as *const u64, 4) };
+    simd_slice_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
 
+#[inline(always)]
+pub fn simd_slice_compare_chunks(a_chunks: &[u64; 4], b_chunks: &[u64; 4]) -> bool {
     // Direct slice comparison
     a_chunks == b_chunks
 }
@@ -175,9 +234,11 @@ pub fn simd_slice_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
 // Integer type variants for SIMD iterator comparison
 #[inline(always)]
 pub fn simd_iterator_u16_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let a_chunks = unsafe { std::slice::from_raw_parts(a.as_ptr() as *const u16, 16) };
-    let b_chunks = unsafe { std::slice::from_raw_parts(b.as_ptr() as *const u16, 16) };
+    simd_iterator_u16_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
 
+#[inline(always)]
+pub fn simd_iterator_u16_compare_chunks(a_chunks: &[u16; 16], b_chunks: &[u16; 16]) -> bool {
     // Iterate over 16 u16 chunks with early exit
     for i in 0..16 {
         if a_chunks[i] != b_chunks[i] {
@@ -189,9 +250,11 @@ pub fn simd_iterator_u16_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
 
 #[inline(always)]
 pub fn simd_iterator_u32_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let a_chunks = unsafe { std::slice::from_raw_parts(a.as_ptr() as *const u32, 8) };
-    let b_chunks = unsafe { std::slice::from_raw_parts(b.as_ptr() as *const u32, 8) };
+    simd_iterator_u32_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
 
+#[inline(always)]
+pub fn simd_iterator_u32_compare_chunks(a_chunks: &[u32; 8], b_chunks: &[u32; 8]) -> bool {
     // Iterate over 8 u32 chunks with early exit
     for i in 0..8 {
         if a_chunks[i] != b_chunks[i] {
@@ -203,9 +266,11 @@ pub fn simd_iterator_u32_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
 
 #[inline(always)]
 pub fn simd_iterator_u128_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
-    let a_chunks = unsafe { std::slice::from_raw_parts(a.as_ptr() as *const u128, 2) };
-    let b_chunks = unsafe { std::slice::from_raw_parts(b.as_ptr() as *const u128, 2) };
+    simd_iterator_u128_compare_chunks(&load_chunks(a), &load_chunks(b))
+}
 
+#[inline(always)]
+pub fn simd_iterator_u128_compare_chunks(a_chunks: &[u128; 2], b_chunks: &[u128; 2]) -> bool {
     // Iterate over 2 u128 chunks with early exit
     for i in 0..2 {
         if a_chunks[i] != b_chunks[i] {
@@ -215,12 +280,254 @@ pub fn simd_iterator_u128_compare(a: &[u8; 32], b: &[u8; 32]) -> bool {
     true
 }
 
+// Partial key comparators
+
+/// Equal in the bytes set in `mask`, e.g. a 0 byte at the bump position of
+/// a PDA ignores the bump. Compares u64 chunks with early exit.
+#[inline(always)]
+pub fn masked_eq(a: &[u8; 32], b: &[u8; 32], mask: &[u8; 32]) -> bool {
+    masked_eq_chunks(&load_chunks(a), &load_chunks(b), &load_chunks(mask))
+}
+
+#[inline(always)]
+pub fn masked_eq_chunks(a_chunks: &[u64; 4], b_chunks: &[u64; 4], mask_chunks: &[u64; 4]) -> bool {
+    for i in 0..4 {
+        if (a_chunks[i] ^ b_chunks[i]) & mask_chunks[i] != 0 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Mask of the first `len` bytes for `masked_eq`, `len` above 32 is all 32.
+#[inline(always)]
+pub const fn prefix_mask(len: usize) -> [u8; 32] {
+    let mut mask = [0u8; 32];
+    let mut i = 0;
+    while i < len && i < 32 {
+        mask[i] = 0xff;
+        i += 1;
+    }
+    mask
+}
+
+/// First `LEN` bytes equal. Compares whole u64 chunks with early exit, then
+/// the rest of the prefix in the next chunk.
+///
+/// Has the comparator signature, e.g.
+/// `changelog.find_latest_entry_with(key, None, prefix_eq::<8>)`.
+#[inline(always)]
+pub fn prefix_eq<const LEN: usize>(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    const { assert!(LEN <= 32, "prefix is longer than the key") };
+    let a_chunks: [u64; 4] = load_chunks(a);
+    let b_chunks: [u64; 4] = load_chunks(b);
+
+    for i in 0..LEN / 8 {
+        if a_chunks[i] != b_chunks[i] {
+            return false;
+        }
+    }
+    if LEN == 32 {
+        return true;
+    }
+    // First LEN % 8 bytes of the chunk in memory order on any endianness,
+    // no bytes if LEN is a multiple of 8
+    let mut rest = [0u8; 8];
+    let mut i = 0;
+    while i < LEN % 8 {
+        rest[i] = 0xff;
+        i += 1;
+    }
+    (a_chunks[LEN / 8] ^ b_chunks[LEN / 8]) & u64::from_ne_bytes(rest) == 0
+}
+
+// Byte slice comparators, used by the strategies for keys that are not [u8; 32]
+
+/// Early-exit byte loop.
+#[inline(always)]
+pub fn bytes_compare(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    for (a, b) in a.iter().zip(b) {
+        if a != b {
+            return false;
+        }
+    }
+    true
+}
+
+/// Ors the xor of every byte pair, no early exit.
+#[inline(always)]
+pub fn branchless_bytes_compare(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (a, b) in a.iter().zip(b) {
+        diff |= a ^ b;
+    }
+    diff == 0
+}
+
+#[inline(always)]
+pub fn sol_memcmp_bytes_compare(a: &[u8], b: &[u8]) -> bool {
+    use crate::memory::sol_memcmp;
+    a.len() == b.len() && sol_memcmp(a, b, a.len()) == 0
+}
+
+/// Compares `C` sized chunks with early exit, then the bytes that don't fill a
+/// chunk.
+#[inline(always)]
+pub fn chunked_compare<C: FromBytes + PartialEq>(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut a_chunks = a.chunks_exact(core::mem::size_of::<C>());
+    let mut b_chunks = b.chunks_exact(core::mem::size_of::<C>());
+    for (a_chunk, b_chunk) in (&mut a_chunks).zip(&mut b_chunks) {
+        let (Ok(a_chunk), Ok(b_chunk)) = (C::read_from_bytes(a_chunk), C::read_from_bytes(b_chunk))
+        else {
+            return false;
+        };
+        if a_chunk != b_chunk {
+            return false;
+        }
+    }
+    a_chunks.remainder() == b_chunks.remainder()
+}
+
+#[inline(always)]
+pub fn pointer_equality_bytes_compare(a: &[u8], b: &[u8]) -> bool {
+    core::ptr::eq(a, b) || chunked_compare::<u128>(a, b)
+}
+
+#[inline(always)]
+pub fn combined_fast_bytes_compare(a: &[u8], b: &[u8]) -> bool {
+    core::ptr::eq(a, b) || sol_memcmp_bytes_compare(a, b)
+}
+
+/// `key` as `[u8; 32]` if `K` is 32 bytes.
+///
+/// The size check is a constant, every key type compiles to one of the two
+/// branches.
+#[inline(always)]
+pub fn key_bytes_32<K: IntoBytes + Immutable>(key: &K) -> Option<&[u8; 32]> {
+    if const { core::mem::size_of::<K>() == 32 } {
+        // SAFETY: `K` is 32 initialized bytes without interior mutability
+        // (`IntoBytes + Immutable`), `[u8; 32]` has alignment 1.
+        Some(unsafe { &*(key as *const K as *const [u8; 32]) })
+    } else {
+        None
+    }
+}
+
+/// Compares two keys, with `compare` if `K` is 32 bytes and with `fallback`
+/// on the key bytes otherwise.
+///
+/// Dispatches on `size_of::<K>()` at compile time, `[u8; 32]` keys call
+/// `compare` directly.
+#[inline(always)]
+pub fn compare_keys<K: IntoBytes + Immutable>(
+    a: &K,
+    b: &K,
+    compare: impl Fn(&[u8; 32], &[u8; 32]) -> bool,
+    fallback: impl Fn(&[u8], &[u8]) -> bool,
+) -> bool {
+    match (key_bytes_32(a), key_bytes_32(b)) {
+        (Some(a), Some(b)) => compare(a, b),
+        _ => fallback(a.as_bytes(), b.as_bytes()),
+    }
+}
+
+/// Search key as `N` chunks of `C`, loaded once per lookup and compared
+/// with `compare_key_chunks`. Zeroed if `K` is not 32 bytes, those keys are
+/// compared as byte slices.
+#[inline(always)]
+pub fn search_key_chunks<K: IntoBytes + Immutable, C: FromBytes, const N: usize>(
+    key: &K,
+) -> [C; N] {
+    match key_bytes_32(key) {
+        Some(key) => load_chunks(key),
+        None => FromZeros::new_zeroed(),
+    }
+}
+
+/// `compare_keys` against a search key loaded by `search_key_chunks`, only
+/// the entry key is loaded per comparison.
+#[inline(always)]
+pub fn compare_key_chunks<K: IntoBytes + Immutable, C: FromBytes, const N: usize>(
+    entry_key: &K,
+    key: &K,
+    key_chunks: &[C; N],
+    compare: impl Fn(&[C; N], &[C; N]) -> bool,
+    fallback: impl Fn(&[u8], &[u8]) -> bool,
+) -> bool {
+    match key_bytes_32(entry_key) {
+        Some(entry_key) => compare(&load_chunks(entry_key), key_chunks),
+        None => fallback(entry_key.as_bytes(), key.as_bytes()),
+    }
+}
+
 pub trait KeyValue {
     type Key: PartialEq;
     type Value: Copy;
 
-    fn key(&self) -> Self::Key;
-    fn value(&self) -> Self::Value;
+    /// Borrows the key in place, lookups compare through it instead of
+    /// copying the key out of the account.
+    fn key_ref(&self) -> &Self::Key;
+    fn value_ref(&self) -> &Self::Value;
+
+    #[inline(always)]
+    fn key(&self) -> Self::Key
+    where
+        Self::Key: Copy,
+    {
+        *self.key_ref()
+    }
+
+    #[inline(always)]
+    fn value(&self) -> Self::Value {
+        *self.value_ref()
+    }
+}
+
+/// `#[derive(KeyValue)]` with `#[key]` and `#[value]` field attributes.
+pub use optimize_cmp_derive::KeyValue;
+
+/// Entry with a `[u8; 32]` key field, enables `find_latest_direct_field_access`
+/// and `find_latest_entry_with`.
+///
+/// `#[derive(KeyValue)]` implements it when the `#[key]` field is a `[u8; 32]`.
+pub trait DirectFieldAccess: KeyValue<Key = [u8; 32]> {
+    fn key_field(&self) -> &[u8; 32];
+}
+
+/// Key made of several `[u8; 32]` fields, e.g. (owner, mint), compared one
+/// field at a time by `find_latest_composite`.
+pub trait CompositeKey {
+    const FIELDS: usize;
+
+    /// Field `index` in comparison order. Put the most selective field first,
+    /// the one that differs between most entries, so most comparisons exit
+    /// after it.
+    fn field(&self, index: usize) -> &[u8; 32];
+}
+
+/// Compares the fields of `a` and `b` in `CompositeKey` order with `compare`,
+/// exits at the first field that differs.
+#[inline(always)]
+pub fn composite_compare<K: CompositeKey>(
+    a: &K,
+    b: &K,
+    compare: impl Fn(&[u8; 32], &[u8; 32]) -> bool,
+) -> bool {
+    for i in 0..K::FIELDS {
+        if !compare(a.field(i), b.field(i)) {
+            return false;
+        }
+    }
+    true
 }
 
 /// Size: 8 + 4 + Entry::LEN * entries
@@ -232,22 +539,46 @@ pub struct GenericChangelog<'a, T: KeyValue + ZeroCopyTraits> {
 
 impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     #[inline(always)]
-    pub fn new(
-        capacity: u64,
-        backing_store: &'a mut [u8],
-    ) -> Result<Self, light_zero_copy::errors::ZeroCopyError> {
+    pub fn new(capacity: u64, backing_store: &'a mut [u8]) -> Result<Self, ChangelogError> {
+        if capacity == 0 {
+            return Err(ChangelogError::InvalidCapacity);
+        }
+        if backing_store.len() < ZeroCopyCyclicVecU64::<T>::required_size_for_capacity(capacity) {
+            return Err(ChangelogError::AccountTooSmall);
+        }
         Ok(Self {
             entries: ZeroCopyCyclicVecU64::<T>::new(capacity, backing_store)?,
         })
     }
 
     #[inline(always)]
-    pub fn from_bytes(
+    pub fn from_bytes(backing_store: &'a mut [u8]) -> Result<Self, ChangelogError> {
+        let entries = ZeroCopyCyclicVecU64::<T>::from_bytes(backing_store)?;
+        // A zeroed account deserializes with capacity 0.
+        if entries.capacity() == 0 {
+            return Err(ChangelogError::UninitializedHeader);
+        }
+        if !valid_ring_header(
+            entries.current_index() as u64,
+            entries.len() as u64,
+            entries.capacity() as u64,
+        ) {
+            return Err(ChangelogError::UninitializedHeader);
+        }
+        Ok(Self { entries })
+    }
+
+    /// Like `from_bytes` but fails if the stored capacity is not `capacity`.
+    #[inline(always)]
+    pub fn from_bytes_with_capacity(
         backing_store: &'a mut [u8],
-    ) -> Result<Self, light_zero_copy::errors::ZeroCopyError> {
-        Ok(Self {
-            entries: ZeroCopyCyclicVecU64::<T>::from_bytes(backing_store)?,
-        })
+        capacity: u64,
+    ) -> Result<Self, ChangelogError> {
+        let changelog = Self::from_bytes(backing_store)?;
+        if changelog.capacity() as u64 != capacity {
+            return Err(ChangelogError::CapacityMismatch);
+        }
+        Ok(changelog)
     }
 
     #[inline(always)]
@@ -255,6 +586,388 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
         self.entries.push(entry);
     }
 
+    /// Read-only view used by all lookups.
+    #[inline(always)]
+    pub fn view(&self) -> GenericChangelogRef<'_, T> {
+        GenericChangelogRef {
+            entries: CyclicEntriesRef {
+                slice: self.entries.as_slice(),
+                last_index: self.entries.last_index(),
+                capacity: self.entries.capacity(),
+            },
+        }
+    }
+
+    #[inline(always)]
+    pub fn find_latest<const USE_MANUAL_COMPARISON: bool>(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T::Key: ManualCompare,
+    {
+        self.view().find_latest::<USE_MANUAL_COMPARISON>(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_composite(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+        compare: impl Fn(&[u8; 32], &[u8; 32]) -> bool,
+    ) -> Option<T::Value>
+    where
+        T::Key: CompositeKey,
+    {
+        self.view().find_latest_composite(key, num_iters, compare)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_masked(
+        &self,
+        key: [u8; 32],
+        mask: [u8; 32],
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T: KeyValue<Key = [u8; 32]>,
+    {
+        self.view().find_latest_masked(key, mask, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_by_prefix(&self, prefix: &[u8], num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T: KeyValue<Key = [u8; 32]>,
+    {
+        self.view().find_latest_by_prefix(prefix, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_unrolled(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_unrolled(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_simd(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_branchless(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_branchless(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_unsafe(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_unsafe(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_sol_memcmp(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_sol_memcmp(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_u128_cast(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_u128_cast(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_pointer_equality(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_pointer_equality(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_combined_fast(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_combined_fast(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd_iterator(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_simd_iterator(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd_iterator_with_cu_tracking(
+        &self,
+        key: [u8; 32],
+        num_iters: Option<usize>,
+        checkpoints: CuCheckpoints,
+        checkpoint: impl FnMut(CuCheckpoint),
+    ) -> Option<u64>
+    where
+        T: KeyValue<Key = [u8; 32], Value = u64>,
+    {
+        self.view().find_latest_simd_iterator_with_cu_tracking(
+            key,
+            num_iters,
+            checkpoints,
+            checkpoint,
+        )
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd_zip(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_simd_zip(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd_slice(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_simd_slice(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd_iterator_u16(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_simd_iterator_u16(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd_iterator_u32(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_simd_iterator_u32(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn find_latest_simd_iterator_u128(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T::Key: IntoBytes + Immutable,
+    {
+        self.view().find_latest_simd_iterator_u128(key, num_iters)
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+}
+
+/// Header invariants of a ring written by `push`: until the ring is full the
+/// next write goes to `length`, lookups index entries from `current_index`.
+#[inline(always)]
+fn valid_ring_header(current_index: u64, length: u64, capacity: u64) -> bool {
+    length <= capacity
+        && current_index < capacity
+        && (length == capacity || current_index == length)
+}
+
+/// Read-only cyclic entries, same layout as `ZeroCopyCyclicVecU64`:
+/// `[current_index, length, capacity]` as u64 followed by `capacity` entries.
+pub struct CyclicEntriesRef<'a, T> {
+    /// The first `length` entries of the ring.
+    slice: &'a [T],
+    last_index: usize,
+    capacity: usize,
+}
+
+impl<'a, T: ZeroCopyTraits> CyclicEntriesRef<'a, T> {
+    #[inline(always)]
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ChangelogError> {
+        let (metadata, _) =
+            <[u64; 3]>::ref_from_prefix(bytes).map_err(|_| ChangelogError::AccountTooSmall)?;
+        let [current_index, length, capacity] = *metadata;
+        if capacity == 0 {
+            return Err(ChangelogError::UninitializedHeader);
+        }
+        if !valid_ring_header(current_index, length, capacity) {
+            return Err(ChangelogError::UninitializedHeader);
+        }
+        // Entries start at the first offset after the header aligned for T.
+        let offset = core::mem::size_of::<[u64; 3]>().next_multiple_of(core::mem::align_of::<T>());
+        let capacity = usize::try_from(capacity).map_err(|_| ChangelogError::InvalidCapacity)?;
+        let (slice, _) = bytes
+            .get(offset..)
+            .and_then(|bytes| <[T]>::ref_from_prefix_with_elems(bytes, capacity).ok())
+            .ok_or(ChangelogError::AccountTooSmall)?;
+        let (current_index, length) = (current_index as usize, length as usize);
+        let last_index = if current_index == 0 && length == capacity {
+            capacity - 1
+        } else if current_index == 0 {
+            0
+        } else {
+            current_index - 1
+        };
+        Ok(Self {
+            slice: &slice[..length],
+            last_index,
+            capacity,
+        })
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline(always)]
+    pub fn last_index(&self) -> usize {
+        self.last_index
+    }
+
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        self.slice.get(index)
+    }
+
+    /// Entries in storage order, use `iter_newest_first` for ring order.
+    #[inline(always)]
+    pub fn iter(&self) -> core::slice::Iter<'a, T> {
+        self.slice.iter()
+    }
+
+    /// Entries from the newest to the oldest, `last_index` down to 0, then
+    /// the older entries from the end of a wrapped ring.
+    #[inline(always)]
+    pub fn iter_newest_first(&self) -> impl Iterator<Item = &'a T> {
+        let (newer, older) = self
+            .slice
+            .split_at((self.last_index + 1).min(self.slice.len()));
+        newer.iter().rev().chain(older.iter().rev())
+    }
+}
+
+impl<T> core::ops::Index<usize> for CyclicEntriesRef<'_, T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &T {
+        &self.slice[index]
+    }
+}
+
+/// Where `find_latest_simd_iterator_with_cu_tracking` calls its checkpoint callback.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CuCheckpoints {
+    /// Checkpoint after every `every_entries` compared entries, 0 disables.
+    pub every_entries: u32,
+    /// Checkpoint after every compared u64 chunk.
+    pub per_chunk: bool,
+}
+
+/// Scan position passed to the checkpoint callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CuCheckpoint {
+    /// Entries compared before the current one.
+    pub entries: usize,
+    /// u64 chunks of the current entry compared so far, 0 between entries.
+    pub chunks: usize,
+}
+
+impl CuCheckpoint {
+    #[inline(always)]
+    pub fn entry(entries: usize) -> Self {
+        Self { entries, chunks: 0 }
+    }
+}
+
+/// Read-only changelog view.
+///
+/// Borrows account data immutably so lookups don't require a writable account.
+pub struct GenericChangelogRef<'a, T: KeyValue + ZeroCopyTraits> {
+    pub entries: CyclicEntriesRef<'a, T>,
+}
+
+impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelogRef<'a, T> {
+    #[inline(always)]
+    pub fn from_bytes(backing_store: &'a [u8]) -> Result<Self, ChangelogError> {
+        Ok(Self {
+            entries: CyclicEntriesRef::from_bytes(backing_store)?,
+        })
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
     // Search backwards from latest_index for up to num_iters
     // None = search all
     // USE_MANUAL_COMPARISON: true = manual loop, false = rust built-in ==
@@ -267,18 +980,17 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     where
         T::Key: ManualCompare,
     {
-        let iter = if let Some(num_iters) = num_iters {
-            self.entries.iter().rev().take(num_iters)
-        } else {
-            self.entries.iter().rev().take(self.entries.len())
-        };
+        let iter = self
+            .entries
+            .iter_newest_first()
+            .take(num_iters.unwrap_or(self.entries.len()));
         for entry in iter {
             let keys_match = if USE_MANUAL_COMPARISON {
                 // Manual loop comparison with early exit
-                entry.key().manual_compare(&key)
+                entry.key_ref().manual_compare(&key)
             } else {
                 // Rust built-in comparison
-                entry.key() == key
+                *entry.key_ref() == key
             };
             if keys_match {
                 return Some(entry.value());
@@ -288,11 +1000,73 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
         None
     }
 
+    /// Newest entry with `key`, compares the `CompositeKey` fields one at a
+    /// time with `compare`, most selective first.
+    #[inline(always)]
+    pub fn find_latest_composite(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+        compare: impl Fn(&[u8; 32], &[u8; 32]) -> bool,
+    ) -> Option<T::Value>
+    where
+        T::Key: CompositeKey,
+    {
+        let iter = self
+            .entries
+            .iter_newest_first()
+            .take(num_iters.unwrap_or(self.entries.len()));
+        for entry in iter {
+            if composite_compare(entry.key_ref(), &key, &compare) {
+                return Some(entry.value());
+            }
+        }
+
+        None
+    }
+
+    /// Newest entry whose key equals `key` in the bytes set in `mask`.
+    #[inline(always)]
+    pub fn find_latest_masked(
+        &self,
+        key: [u8; 32],
+        mask: [u8; 32],
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
+    where
+        T: KeyValue<Key = [u8; 32]>,
+    {
+        let (key, mask): ([u64; 4], [u64; 4]) = (load_chunks(&key), load_chunks(&mask));
+        let iter = self
+            .entries
+            .iter_newest_first()
+            .take(num_iters.unwrap_or(self.entries.len()));
+        for entry in iter {
+            if masked_eq_chunks(&load_chunks(entry.key_ref()), &key, &mask) {
+                return Some(entry.value());
+            }
+        }
+
+        None
+    }
+
+    /// Newest entry whose key starts with `prefix`, `None` if `prefix` is
+    /// longer than 32 bytes.
+    #[inline(always)]
+    pub fn find_latest_by_prefix(&self, prefix: &[u8], num_iters: Option<usize>) -> Option<T::Value>
+    where
+        T: KeyValue<Key = [u8; 32]>,
+    {
+        let mut key = [0u8; 32];
+        key.get_mut(..prefix.len())?.copy_from_slice(prefix);
+        self.find_latest_masked(key, prefix_mask(prefix.len()), num_iters)
+    }
+
     // Optimization variants - Direct comparison methods without trait overhead
     #[inline(always)]
-    pub fn find_latest_unrolled(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_unrolled(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -307,7 +1081,12 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if unrolled_compare(&entry.key(), &key) {
+                if compare_keys(
+                    entry.key_ref(),
+                    &key,
+                    unrolled_compare,
+                    bytes_compare,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -329,9 +1108,9 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_simd(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_simd(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -341,12 +1120,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u64; 4] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if simd_style_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    simd_style_compare_chunks,
+                    chunked_compare::<u64>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -368,9 +1154,9 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_branchless(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_branchless(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -385,7 +1171,12 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if branchless_compare(&entry.key(), &key) {
+                if compare_keys(
+                    entry.key_ref(),
+                    &key,
+                    branchless_compare,
+                    branchless_bytes_compare,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -407,9 +1198,9 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_unsafe(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_unsafe(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -424,7 +1215,12 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if unsafe_fast_compare(&entry.key(), &key) {
+                if compare_keys(
+                    entry.key_ref(),
+                    &key,
+                    unsafe_fast_compare,
+                    bytes_compare,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -447,9 +1243,9 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
     // P-Token inspired optimization methods
     #[inline(always)]
-    pub fn find_latest_sol_memcmp(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_sol_memcmp(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -464,7 +1260,12 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if sol_memcmp_compare(&entry.key(), &key) {
+                if compare_keys(
+                    entry.key_ref(),
+                    &key,
+                    sol_memcmp_compare,
+                    sol_memcmp_bytes_compare,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -486,9 +1287,9 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_u128_cast(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_u128_cast(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -498,12 +1299,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u128; 2] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if u128_cast_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    u128_cast_compare_chunks,
+                    chunked_compare::<u128>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -527,11 +1335,11 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     #[inline(always)]
     pub fn find_latest_pointer_equality(
         &self,
-        key: [u8; 32],
+        key: T::Key,
         num_iters: Option<usize>,
-    ) -> Option<u64>
+    ) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -541,12 +1349,21 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u128; 2] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if pointer_equality_compare(&entry.key(), &key) {
+                if core::ptr::eq(entry.key_ref(), &key)
+                    || compare_key_chunks(
+                        entry.key_ref(),
+                        &key,
+                        &key_chunks,
+                        u128_cast_compare_chunks,
+                        chunked_compare::<u128>,
+                    )
+                {
                     return Some(entry.value());
                 }
             }
@@ -568,9 +1385,13 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_combined_fast(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_combined_fast(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -585,7 +1406,12 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if combined_fast_compare(&entry.key(), &key) {
+                if compare_keys(
+                    entry.key_ref(),
+                    &key,
+                    combined_fast_compare,
+                    combined_fast_bytes_compare,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -608,9 +1434,13 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
     // Additional SIMD iteration variants
     #[inline(always)]
-    pub fn find_latest_simd_iterator(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_simd_iterator(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -620,12 +1450,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u64; 4] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if simd_iterator_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    simd_iterator_compare_chunks,
+                    chunked_compare::<u64>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -646,23 +1483,61 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
         None
     }
 
-    // Detailed CU tracking version for debugging
+    /// `find_latest_simd_iterator` that calls `checkpoint` at the start, at
+    /// the positions selected by `checkpoints` and when the scan returns.
+    ///
+    /// The callback is where the program logs remaining compute units, the
+    /// difference between two checkpoints is the cost of the scan in between.
     #[inline(always)]
-    pub fn find_latest_simd_iterator_with_cu_tracking(&self, key: [u8; 32]) -> Option<u64>
+    pub fn find_latest_simd_iterator_with_cu_tracking(
+        &self,
+        key: [u8; 32],
+        num_iters: Option<usize>,
+        checkpoints: CuCheckpoints,
+        mut checkpoint: impl FnMut(CuCheckpoint),
+    ) -> Option<u64>
     where
         T: KeyValue<Key = [u8; 32], Value = u64>,
     {
+        let max_iters = num_iters
+            .unwrap_or(self.entries.len())
+            .min(self.entries.len());
+
+        checkpoint(CuCheckpoint::entry(0));
+        if max_iters == 0 || self.entries.is_empty() {
+            return None;
+        }
+
+        let every_entries = checkpoints.every_entries as usize;
+        let key_chunks: [u64; 4] = load_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
+        let mut result = None;
 
-        while iterations < self.entries.len() {
+        while iterations < max_iters {
             let entry = &self.entries[current_index];
-            if simd_iterator_compare(&entry.key(), &key) {
-                return Some(entry.value());
-            }
+            let entry_chunks = load_chunks(entry.key_ref());
+            let equal = if checkpoints.per_chunk {
+                simd_iterator_compare_chunks_with_checkpoints(&entry_chunks, &key_chunks, |chunks| {
+                    checkpoint(CuCheckpoint {
+                        entries: iterations,
+                        chunks,
+                    })
+                })
+            } else {
+                simd_iterator_compare_chunks(&entry_chunks, &key_chunks)
+            };
 
             iterations += 1;
-            if iterations < self.entries.len() {
+            if equal {
+                result = Some(entry.value());
+                break;
+            }
+            if every_entries != 0 && iterations % every_entries == 0 {
+                checkpoint(CuCheckpoint::entry(iterations));
+            }
+
+            if iterations < max_iters {
                 if current_index == 0 {
                     if self.entries.len() == self.entries.capacity() {
                         current_index = self.entries.capacity() - 1;
@@ -674,13 +1549,17 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
                 }
             }
         }
-        None
+
+        if result.is_some() || every_entries == 0 || iterations % every_entries != 0 {
+            checkpoint(CuCheckpoint::entry(iterations));
+        }
+        result
     }
 
     #[inline(always)]
-    pub fn find_latest_simd_zip(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_simd_zip(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -690,12 +1569,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u64; 4] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if simd_zip_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    simd_zip_compare_chunks,
+                    chunked_compare::<u64>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -717,9 +1603,9 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_simd_slice(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_simd_slice(&self, key: T::Key, num_iters: Option<usize>) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -729,12 +1615,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u64; 4] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if simd_slice_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    simd_slice_compare_chunks,
+                    chunked_compare::<u64>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -757,9 +1650,13 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
 
     // Integer type variants for SIMD iterator methods
     #[inline(always)]
-    pub fn find_latest_simd_iterator_u16(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_simd_iterator_u16(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -769,12 +1666,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u16; 16] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if simd_iterator_u16_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    simd_iterator_u16_compare_chunks,
+                    chunked_compare::<u16>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -796,9 +1700,13 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_simd_iterator_u32(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_simd_iterator_u32(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -808,12 +1716,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u32; 8] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if simd_iterator_u32_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    simd_iterator_u32_compare_chunks,
+                    chunked_compare::<u32>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -835,9 +1750,13 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
     }
 
     #[inline(always)]
-    pub fn find_latest_simd_iterator_u128(&self, key: [u8; 32], num_iters: Option<usize>) -> Option<u64>
+    pub fn find_latest_simd_iterator_u128(
+        &self,
+        key: T::Key,
+        num_iters: Option<usize>,
+    ) -> Option<T::Value>
     where
-        T: KeyValue<Key = [u8; 32], Value = u64>,
+        T::Key: IntoBytes + Immutable,
     {
         let max_iters = num_iters
             .unwrap_or(self.entries.len())
@@ -847,12 +1766,19 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
             return None;
         }
 
+        let key_chunks: [u128; 2] = search_key_chunks(&key);
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
         while iterations < max_iters {
             if let Some(entry) = self.entries.get(current_index) {
-                if simd_iterator_u128_compare(&entry.key(), &key) {
+                if compare_key_chunks(
+                    entry.key_ref(),
+                    &key,
+                    &key_chunks,
+                    simd_iterator_u128_compare_chunks,
+                    chunked_compare::<u128>,
+                ) {
                     return Some(entry.value());
                 }
             }
@@ -872,22 +1798,14 @@ impl<'a, T: KeyValue + ZeroCopyTraits> GenericChangelog<'a, T> {
         }
         None
     }
-
-    #[inline(always)]
-    pub fn len(&self) -> usize {
-        self.entries.len()
-    }
-
-    #[inline(always)]
-    pub fn capacity(&self) -> usize {
-        self.entries.capacity()
-    }
 }
 
-#[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes)]
+#[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes, KeyValue)]
 #[repr(C)]
 pub struct Entry {
+    #[value]
     pub value: u64,
+    #[key]
     pub mint: [u8; 32],
 }
 
@@ -897,8 +1815,9 @@ impl Entry {
         Self { value, mint }
     }
 
+    #[cfg(feature = "solana-program")]
     #[inline(always)]
-    pub fn new_from_pubkey(mint: Pubkey, value: u64) -> Self {
+    pub fn new_from_pubkey(mint: solana_program::pubkey::Pubkey, value: u64) -> Self {
         Self {
             value,
             mint: mint.to_bytes(),
@@ -906,38 +1825,102 @@ impl Entry {
     }
 }
 
-impl KeyValue for Entry {
-    type Value = u64;
-    type Key = [u8; 32];
+/// (owner, mint) key, compares `mint` first since one owner usually holds
+/// many mints.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, KnownLayout, Immutable, FromBytes, IntoBytes)]
+#[repr(C)]
+pub struct OwnerMint {
+    pub owner: [u8; 32],
+    pub mint: [u8; 32],
+}
+
+impl CompositeKey for OwnerMint {
+    const FIELDS: usize = 2;
+
+    #[inline(always)]
+    fn field(&self, index: usize) -> &[u8; 32] {
+        match index {
+            0 => &self.mint,
+            _ => &self.owner,
+        }
+    }
+}
+
+/// Entry keyed by an (owner, mint) pair.
+#[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes, KeyValue)]
+#[repr(C)]
+pub struct OwnerMintEntry {
+    #[value]
+    pub value: u64,
+    #[key]
+    pub key: OwnerMint,
+}
 
+// Direct field access for entries with a `[u8; 32]` key field
+impl<T: DirectFieldAccess + ZeroCopyTraits> GenericChangelog<'_, T> {
     #[inline(always)]
-    fn key(&self) -> [u8; 32] {
-        self.mint
+    pub fn find_latest_direct_field_access(
+        &self,
+        key: [u8; 32],
+        num_iters: Option<usize>,
+    ) -> Option<T::Value> {
+        self.view().find_latest_direct_field_access(key, num_iters)
     }
 
     #[inline(always)]
-    fn value(&self) -> Self::Value {
-        self.value
+    pub fn find_latest_entry_with(
+        &self,
+        key: [u8; 32],
+        num_iters: Option<usize>,
+        compare: impl Fn(&[u8; 32], &[u8; 32]) -> bool,
+    ) -> Option<T::Value> {
+        self.view().find_latest_entry_with(key, num_iters, compare)
     }
 }
 
-// Specific implementation for Entry type with direct field access
-impl GenericChangelog<'_, Entry> {
-    // Non-generic version that directly accesses Entry struct fields
+impl<T: DirectFieldAccess + ZeroCopyTraits> GenericChangelogRef<'_, T> {
+    // Accesses the key field directly instead of through KeyValue
+    #[inline(always)]
+    pub fn find_latest_direct_field_access(
+        &self,
+        key: [u8; 32],
+        num_iters: Option<usize>,
+    ) -> Option<T::Value> {
+        let key_chunks: [u64; 4] = load_chunks(&key);
+        self.find_latest_entry_with(key, num_iters, |entry_key, _| {
+            simd_iterator_compare_chunks(&load_chunks(entry_key), &key_chunks)
+        })
+    }
+
+    /// Fast path of any comparator: compares the key field in place, e.g.
+    /// `entry.mint`, instead of going through `KeyValue`.
     #[inline(always)]
-    pub fn find_latest_direct_field_access(&self, key: [u8; 32]) -> Option<u64> {
+    pub fn find_latest_entry_with(
+        &self,
+        key: [u8; 32],
+        num_iters: Option<usize>,
+        compare: impl Fn(&[u8; 32], &[u8; 32]) -> bool,
+    ) -> Option<T::Value> {
+        let max_iters = num_iters
+            .unwrap_or(self.entries.len())
+            .min(self.entries.len());
+
+        if max_iters == 0 {
+            return None;
+        }
+
         let mut current_index = self.entries.last_index();
         let mut iterations = 0;
 
-        while iterations < self.entries.len() {
+        while iterations < max_iters {
             let entry = &self.entries[current_index];
             // Direct field access instead of trait methods
-            if simd_iterator_compare(&entry.mint, &key) {
-                return Some(entry.value);
+            if compare(entry.key_field(), &key) {
+                return Some(entry.value());
             }
 
             iterations += 1;
-            if iterations < self.entries.len() {
+            if iterations < max_iters {
                 if current_index == 0 {
                     if self.entries.len() == self.entries.capacity() {
                         current_index = self.entries.capacity() - 1;
@@ -956,6 +1939,7 @@ impl GenericChangelog<'_, Entry> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::AlignedBytes;
     use light_zero_copy::cyclic_vec::ZeroCopyCyclicVecU64;
 
     fn create_test_pubkey(seed: u8) -> [u8; 32] {
@@ -968,7 +1952,7 @@ mod tests {
     fn test_generic_changelog_basic() {
         let capacity = 5u64;
         let mut backing_store =
-            vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
         let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
 
         let mint1 = create_test_pubkey(1);
@@ -994,7 +1978,7 @@ mod tests {
     fn test_generic_changelog_overwrites() {
         let capacity = 3u64;
         let mut backing_store =
-            vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
         let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
 
         let mint1 = create_test_pubkey(1);
@@ -1014,7 +1998,7 @@ mod tests {
     fn test_generic_changelog_cyclic_behavior() {
         let capacity = 3u64;
         let mut backing_store =
-            vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
         let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
 
         let mint1 = create_test_pubkey(1);
@@ -1041,7 +2025,7 @@ mod tests {
     fn test_generic_changelog_limited_search() {
         let capacity = 10u64;
         let mut backing_store =
-            vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
         let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
 
         let mint1 = create_test_pubkey(1);
@@ -1074,7 +2058,7 @@ mod tests {
     fn test_edge_cases() {
         let capacity = 5u64;
         let mut backing_store =
-            vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
 
         // Test empty changelog
         let changelog: GenericChangelog<'_, Entry> =
@@ -1084,7 +2068,7 @@ mod tests {
 
         // Test single entry
         let mut backing_store2 =
-            vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
         let mut changelog = GenericChangelog::new(capacity, &mut backing_store2).unwrap();
         changelog.push(Entry::new(mint1, 42));
         assert_eq!(changelog.find_latest::<false>(mint1, None), Some(42));
@@ -1096,7 +2080,7 @@ mod tests {
     fn test_reverse_search_order() {
         let capacity = 5u64;
         let mut backing_store =
-            vec![0u8; ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity)];
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
         let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
 
         let mint1 = create_test_pubkey(1);
@@ -1113,4 +2097,865 @@ mod tests {
         assert_eq!(changelog.find_latest::<false>(mint1, Some(1)), Some(300)); // Only check last
         assert_eq!(changelog.find_latest::<false>(mint1, Some(2)), Some(300)); // Check last 2, still finds 300
     }
+
+    #[test]
+    fn test_find_latest_after_multiple_wraps() {
+        use crate::strategy::Strategy;
+
+        let capacity = 5u64;
+        let mut backing_store =
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
+        let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+
+        let [a, b, c, d] = [1, 2, 3, 4].map(create_test_pubkey);
+        // Wrap three times, then slots hold [20 c, 21 a, 22 b | 18 b, 19 a]
+        // with the head at slot 3
+        for value in 0..18 {
+            changelog.push(Entry::new([a, b, d][value as usize % 3], value));
+        }
+        for (key, value) in [(b, 18), (a, 19), (c, 20), (a, 21), (b, 22)] {
+            changelog.push(Entry::new(key, value));
+        }
+        assert_eq!(changelog.entries.last_index(), 2);
+
+        let changelog_ref = GenericChangelogRef::<Entry>::from_bytes(&backing_store).unwrap();
+        let values: Vec<u64> = changelog_ref
+            .entries
+            .iter_newest_first()
+            .map(|entry| entry.value)
+            .collect();
+        assert_eq!(values, [22, 21, 20, 19, 18]);
+
+        let cases = [
+            (a, None, Some(21)),
+            (a, Some(1), None),
+            (a, Some(2), Some(21)),
+            (b, None, Some(22)),
+            (b, Some(1), Some(22)),
+            (c, Some(2), None),
+            (c, Some(3), Some(20)),
+            (d, None, None),
+        ];
+        for (key, num_iters, expected) in cases {
+            assert_eq!(changelog_ref.find_latest::<false>(key, num_iters), expected);
+            assert_eq!(changelog_ref.find_latest::<true>(key, num_iters), expected);
+            for strategy in Strategy::ALL {
+                assert_eq!(
+                    strategy.lookup_fn()(&changelog_ref, key, num_iters),
+                    expected,
+                    "{} {num_iters:?}",
+                    strategy.name()
+                );
+                assert_eq!(
+                    strategy.entry_lookup_fn()(&changelog_ref, key, num_iters),
+                    expected,
+                    "{} entry fast path {num_iters:?}",
+                    strategy.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_newest_first_at_every_head() {
+        let capacity = 4u64;
+        for pushes in 0..4 * capacity {
+            let mut backing_store =
+                AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
+            let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+            for value in 0..pushes {
+                changelog.push(Entry::new(create_test_pubkey(value as u8), value));
+            }
+
+            let values: Vec<u64> = changelog
+                .view()
+                .entries
+                .iter_newest_first()
+                .map(|entry| entry.value)
+                .collect();
+            let expected: Vec<u64> = (0..pushes).rev().take(capacity as usize).collect();
+            assert_eq!(values, expected, "pushes {pushes}");
+        }
+    }
+
+    #[test]
+    fn test_changelog_ref_matches_mutable() {
+        let capacity = 4u64;
+        let mut backing_store =
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
+        let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+
+        // Wrap around once so current_index != len
+        for i in 1..=6u8 {
+            changelog.push(Entry::new(create_test_pubkey(i), i as u64 * 100));
+        }
+        let last_index = changelog.entries.last_index();
+
+        let changelog_ref = GenericChangelogRef::<Entry>::from_bytes(&backing_store).unwrap();
+        assert_eq!(changelog_ref.len(), 4);
+        assert_eq!(changelog_ref.capacity(), 4);
+        assert_eq!(changelog_ref.entries.last_index(), last_index);
+        for i in 1..=6u8 {
+            let key = create_test_pubkey(i);
+            let expected = if i > 2 { Some(i as u64 * 100) } else { None };
+            assert_eq!(changelog_ref.find_latest_simd_iterator(key, None), expected);
+            assert_eq!(changelog_ref.find_latest_direct_field_access(key, None), expected);
+        }
+
+        let zeroed = AlignedBytes::zeroed(backing_store.len());
+        assert_eq!(
+            GenericChangelogRef::<Entry>::from_bytes(&zeroed).err(),
+            Some(ChangelogError::UninitializedHeader)
+        );
+        assert_eq!(
+            GenericChangelogRef::<Entry>::from_bytes(&backing_store[..backing_store.len() - 1])
+                .err(),
+            Some(ChangelogError::AccountTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_changelog_errors() {
+        let capacity = 5u64;
+        let size = ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity);
+
+        let mut backing_store = AlignedBytes::zeroed(size);
+        assert_eq!(
+            GenericChangelog::<Entry>::new(0, &mut backing_store).err(),
+            Some(ChangelogError::InvalidCapacity)
+        );
+
+        let mut too_small = AlignedBytes::zeroed(size - 1);
+        assert_eq!(
+            GenericChangelog::<Entry>::new(capacity, &mut too_small).err(),
+            Some(ChangelogError::AccountTooSmall)
+        );
+
+        // Zeroed account was never initialized
+        let mut zeroed = AlignedBytes::zeroed(size);
+        assert_eq!(
+            GenericChangelog::<Entry>::from_bytes(&mut zeroed).err(),
+            Some(ChangelogError::UninitializedHeader)
+        );
+
+        GenericChangelog::<Entry>::new(capacity, &mut backing_store).unwrap();
+        assert_eq!(
+            GenericChangelog::<Entry>::from_bytes_with_capacity(&mut backing_store, capacity + 1)
+                .err(),
+            Some(ChangelogError::CapacityMismatch)
+        );
+        assert!(
+            GenericChangelog::<Entry>::from_bytes_with_capacity(&mut backing_store, capacity)
+                .is_ok()
+        );
+
+        // Ring is not full but current_index points past the written entries
+        backing_store[..8].copy_from_slice(&3u64.to_le_bytes());
+        backing_store[8..16].copy_from_slice(&1u64.to_le_bytes());
+        assert_eq!(
+            GenericChangelog::<Entry>::from_bytes(&mut backing_store).err(),
+            Some(ChangelogError::UninitializedHeader)
+        );
+        assert_eq!(
+            GenericChangelogRef::<Entry>::from_bytes(&backing_store).err(),
+            Some(ChangelogError::UninitializedHeader)
+        );
+    }
+
+    #[test]
+    fn test_cu_tracking_checkpoints() {
+        let capacity = 10u64;
+        let mut backing_store =
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
+        let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+        for i in 0..10u8 {
+            changelog.push(Entry::new(create_test_pubkey(i), i as u64));
+        }
+        let scan = |key, num_iters, every_entries, per_chunk| {
+            let mut checkpoints = Vec::new();
+            let result = changelog.find_latest_simd_iterator_with_cu_tracking(
+                key,
+                num_iters,
+                CuCheckpoints {
+                    every_entries,
+                    per_chunk,
+                },
+                |c| checkpoints.push((c.entries, c.chunks)),
+            );
+            assert_eq!(result, changelog.find_latest_simd_iterator(key, num_iters));
+            (result, checkpoints)
+        };
+
+        // Key 7 is the third newest entry
+        let key = create_test_pubkey(7);
+        assert_eq!(
+            scan(key, None, 1, false),
+            (Some(7), vec![(0, 0), (1, 0), (2, 0), (3, 0)])
+        );
+        // Mismatches exit after the first chunk, the match compares all four
+        assert_eq!(
+            scan(key, None, 0, true),
+            (
+                Some(7),
+                vec![
+                    (0, 0),
+                    (0, 1),
+                    (1, 1),
+                    (2, 1),
+                    (2, 2),
+                    (2, 3),
+                    (2, 4),
+                    (3, 0)
+                ]
+            )
+        );
+
+        let missing = create_test_pubkey(42);
+        assert_eq!(
+            scan(missing, None, 4, false),
+            (None, vec![(0, 0), (4, 0), (8, 0), (10, 0)])
+        );
+        assert_eq!(
+            scan(missing, Some(8), 4, false),
+            (None, vec![(0, 0), (4, 0), (8, 0)])
+        );
+        assert_eq!(scan(missing, Some(0), 1, true), (None, vec![(0, 0)]));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_strategies_match_reference_model() {
+        use crate::strategy::Strategy;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..2000 {
+            let capacity = rng.gen_range(1..=16u64);
+            let pushes = rng.gen_range(0..=capacity * 4);
+
+            // Keys differ from a common base at a random position, so every
+            // u64 chunk decides some comparisons
+            let base: [u8; 32] = rng.gen();
+            let key_pool: Vec<[u8; 32]> = (0..rng.gen_range(1..=8))
+                .map(|_| {
+                    let mut key = base;
+                    let pos = rng.gen_range(0..32);
+                    key[pos] = key[pos].wrapping_add(rng.gen_range(1..=255));
+                    key
+                })
+                .collect();
+
+            let mut backing_store =
+                AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
+            let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+            let mut model = Vec::new();
+            for value in 0..pushes {
+                let entry = Entry::new(key_pool[rng.gen_range(0..key_pool.len())], value);
+                changelog.push(entry);
+                model.push(entry);
+            }
+            let changelog_ref = GenericChangelogRef::<Entry>::from_bytes(&backing_store).unwrap();
+
+            for key in key_pool.iter().copied().chain([base]) {
+                let random_limit = rng.gen_range(0..=capacity as usize + 2);
+                for num_iters in [None, Some(0), Some(1), Some(random_limit)] {
+                    // Newest retained entries first
+                    let expected = model
+                        .iter()
+                        .rev()
+                        .take(capacity as usize)
+                        .take(num_iters.unwrap_or(usize::MAX))
+                        .find(|entry| entry.mint == key)
+                        .map(|entry| entry.value);
+                    let context = format!(
+                        "capacity {capacity}, pushes {pushes}, num_iters {num_iters:?}"
+                    );
+
+                    for strategy in Strategy::ALL {
+                        assert_eq!(
+                            strategy.lookup_fn()(&changelog_ref, key, num_iters),
+                            expected,
+                            "{}, {context}",
+                            strategy.name()
+                        );
+                        assert_eq!(
+                            strategy.entry_lookup_fn()(&changelog_ref, key, num_iters),
+                            expected,
+                            "{} entry fast path, {context}",
+                            strategy.name()
+                        );
+                    }
+                    assert_eq!(
+                        changelog_ref.find_latest_simd_iterator_with_cu_tracking(
+                            key,
+                            num_iters,
+                            CuCheckpoints::default(),
+                            |_| {}
+                        ),
+                        expected,
+                        "cu_tracking, {context}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// 20 byte key with a u32 value.
+    #[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes, KeyValue)]
+    #[repr(C)]
+    struct AddressEntry {
+        #[key]
+        key: [u8; 20],
+        #[value]
+        value: u32,
+    }
+
+    /// 32 byte key with a 16 byte value.
+    #[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes, KeyValue)]
+    #[repr(C)]
+    struct WideValueEntry {
+        #[key]
+        mint: [u8; 32],
+        #[value]
+        value: [u8; 16],
+    }
+
+    /// Tuple struct with the value first.
+    #[derive(Copy, Clone, KnownLayout, Immutable, FromBytes, IntoBytes, KeyValue)]
+    #[repr(C)]
+    struct TupleEntry(#[value] u64, #[key] [u8; 32]);
+
+    /// Pushes `pushes` entries with keys from `key_pool` and checks every
+    /// strategy against the newest retained entries.
+    fn check_generic_strategies<T, const N: usize>(
+        rng: &mut rand::rngs::StdRng,
+        capacity: u64,
+        pushes: u64,
+        key_pool: &[[u8; N]],
+        new_entry: impl Fn([u8; N], u64) -> T,
+    ) where
+        T: KeyValue<Key = [u8; N]> + ZeroCopyTraits,
+        T::Value: PartialEq + core::fmt::Debug,
+    {
+        use rand::Rng;
+
+        type Lookup<T> = fn(
+            &GenericChangelogRef<T>,
+            <T as KeyValue>::Key,
+            Option<usize>,
+        ) -> Option<<T as KeyValue>::Value>;
+        let strategies: [(&str, Lookup<T>); 16] = [
+            ("partialeq", |c, key, n| c.find_latest::<false>(key, n)),
+            ("manual", |c, key, n| c.find_latest::<true>(key, n)),
+            ("unrolled", |c, key, n| c.find_latest_unrolled(key, n)),
+            ("simd", |c, key, n| c.find_latest_simd(key, n)),
+            ("branchless", |c, key, n| c.find_latest_branchless(key, n)),
+            ("unsafe", |c, key, n| c.find_latest_unsafe(key, n)),
+            ("sol_memcmp", |c, key, n| c.find_latest_sol_memcmp(key, n)),
+            ("u128_cast", |c, key, n| c.find_latest_u128_cast(key, n)),
+            ("pointer_equality", |c, key, n| {
+                c.find_latest_pointer_equality(key, n)
+            }),
+            ("combined_fast", |c, key, n| c.find_latest_combined_fast(key, n)),
+            ("simd_iterator", |c, key, n| c.find_latest_simd_iterator(key, n)),
+            ("simd_zip", |c, key, n| c.find_latest_simd_zip(key, n)),
+            ("simd_slice", |c, key, n| c.find_latest_simd_slice(key, n)),
+            ("simd_iterator_u16", |c, key, n| {
+                c.find_latest_simd_iterator_u16(key, n)
+            }),
+            ("simd_iterator_u32", |c, key, n| {
+                c.find_latest_simd_iterator_u32(key, n)
+            }),
+            ("simd_iterator_u128", |c, key, n| {
+                c.find_latest_simd_iterator_u128(key, n)
+            }),
+        ];
+
+        let mut backing_store =
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<T>::required_size_for_capacity(capacity));
+        let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+        let mut model = Vec::new();
+        for value in 0..pushes {
+            let entry = new_entry(key_pool[rng.gen_range(0..key_pool.len())], value);
+            changelog.push(entry);
+            model.push(entry);
+        }
+        for key in key_pool {
+            let random_limit = rng.gen_range(0..=capacity as usize + 2);
+            for num_iters in [None, Some(0), Some(1), Some(random_limit)] {
+                let expected = model
+                    .iter()
+                    .rev()
+                    .take(capacity as usize)
+                    .take(num_iters.unwrap_or(usize::MAX))
+                    .find(|entry| entry.key() == *key)
+                    .map(|entry| entry.value());
+                for (name, lookup) in strategies {
+                    assert_eq!(
+                        lookup(&changelog.view(), *key, num_iters),
+                        expected,
+                        "{name}, capacity {capacity}, pushes {pushes}, num_iters {num_iters:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_strategies_with_generic_entries() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0x6e6e);
+        for _ in 0..500 {
+            let capacity = rng.gen_range(1..=16u64);
+            let pushes = rng.gen_range(0..=capacity * 4);
+
+            // The pool always has a key only differing in the last byte
+            let base: [u8; 32] = rng.gen();
+            let mut key_pool = vec![base];
+            let mut last = base;
+            last[31] ^= 1;
+            key_pool.push(last);
+            for _ in 0..rng.gen_range(0..6) {
+                let mut key = base;
+                let pos = rng.gen_range(0..32);
+                key[pos] = key[pos].wrapping_add(rng.gen_range(1..=255));
+                key_pool.push(key);
+            }
+
+            let address_pool: Vec<[u8; 20]> = key_pool
+                .iter()
+                .map(|key| key[12..].try_into().unwrap())
+                .collect();
+            check_generic_strategies(&mut rng, capacity, pushes, &address_pool, |key, value| {
+                AddressEntry {
+                    key,
+                    value: value as u32 | 0xff00_0000,
+                }
+            });
+            check_generic_strategies(&mut rng, capacity, pushes, &key_pool, |mint, value| {
+                WideValueEntry {
+                    mint,
+                    value: u128::from(value).to_be_bytes(),
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_derived_direct_field_access() {
+        let capacity = 8u64;
+        let mut backing_store = AlignedBytes::zeroed(
+            ZeroCopyCyclicVecU64::<WideValueEntry>::required_size_for_capacity(capacity),
+        );
+        let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+        for i in 0..12u8 {
+            changelog.push(WideValueEntry {
+                mint: create_test_pubkey(i % 5),
+                value: [i; 16],
+            });
+        }
+        for seed in 0..6 {
+            let key = create_test_pubkey(seed);
+            for num_iters in [None, Some(0), Some(3)] {
+                let expected = changelog.find_latest_simd_iterator(key, num_iters);
+                assert_eq!(changelog.find_latest_direct_field_access(key, num_iters), expected);
+                assert_eq!(
+                    changelog.find_latest_entry_with(key, num_iters, unrolled_compare),
+                    expected
+                );
+            }
+        }
+        assert_eq!(
+            changelog.find_latest_direct_field_access(create_test_pubkey(1), None),
+            Some([11; 16])
+        );
+
+        let mut backing_store = AlignedBytes::zeroed(
+            ZeroCopyCyclicVecU64::<TupleEntry>::required_size_for_capacity(capacity),
+        );
+        let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+        changelog.push(TupleEntry(7, create_test_pubkey(1)));
+        assert_eq!(changelog.view().entries[0].key(), create_test_pubkey(1));
+        assert_eq!(changelog.view().entries[0].value(), 7);
+        assert_eq!(
+            changelog.find_latest_direct_field_access(create_test_pubkey(1), None),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn test_composite_compare_order() {
+        let a = OwnerMint {
+            owner: create_test_pubkey(1),
+            mint: create_test_pubkey(2),
+        };
+        let compared = core::cell::RefCell::new(Vec::new());
+        let compare = |x: &[u8; 32], y: &[u8; 32]| {
+            compared.borrow_mut().push(x[0]);
+            x == y
+        };
+
+        // Mint first, exits before the owner
+        let other_mint = OwnerMint {
+            mint: create_test_pubkey(3),
+            ..a
+        };
+        assert!(!composite_compare(&a, &other_mint, compare));
+        assert_eq!(compared.take(), [2]);
+
+        let other_owner = OwnerMint {
+            owner: create_test_pubkey(3),
+            ..a
+        };
+        assert!(!composite_compare(&a, &other_owner, compare));
+        assert_eq!(compared.take(), [2, 1]);
+
+        assert!(composite_compare(&a, &a, compare));
+        assert_eq!(compared.take(), [2, 1]);
+    }
+
+    #[test]
+    fn test_find_latest_composite() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        type CompareFn = fn(&[u8; 32], &[u8; 32]) -> bool;
+        let comparators: [(&str, CompareFn); 5] = [
+            ("partialeq", |a, b| a == b),
+            ("unrolled", unrolled_compare),
+            ("sol_memcmp", sol_memcmp_compare),
+            ("u128_cast", u128_cast_compare),
+            ("simd_iterator", simd_iterator_compare),
+        ];
+
+        let mut rng = StdRng::seed_from_u64(0x0aa1);
+        for _ in 0..500 {
+            let capacity = rng.gen_range(1..=16u64);
+            let pushes = rng.gen_range(0..=capacity * 4);
+            // Few owners and mints, so keys often share one of the two
+            let owners: Vec<[u8; 32]> = (0..rng.gen_range(1..=3)).map(|_| rng.gen()).collect();
+            let mints: Vec<[u8; 32]> = (0..rng.gen_range(1..=3)).map(|_| rng.gen()).collect();
+            let random_key = |rng: &mut StdRng| OwnerMint {
+                owner: owners[rng.gen_range(0..owners.len())],
+                mint: mints[rng.gen_range(0..mints.len())],
+            };
+
+            let mut backing_store = AlignedBytes::zeroed(
+                ZeroCopyCyclicVecU64::<OwnerMintEntry>::required_size_for_capacity(capacity),
+            );
+            let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+            let mut model = Vec::new();
+            for value in 0..pushes {
+                let entry = OwnerMintEntry {
+                    value,
+                    key: random_key(&mut rng),
+                };
+                changelog.push(entry);
+                model.push(entry);
+            }
+
+            for _ in 0..4 {
+                let key = random_key(&mut rng);
+                let random_limit = rng.gen_range(0..=capacity as usize + 2);
+                for num_iters in [None, Some(0), Some(1), Some(random_limit)] {
+                    let expected = model
+                        .iter()
+                        .rev()
+                        .take(capacity as usize)
+                        .take(num_iters.unwrap_or(usize::MAX))
+                        .find(|entry| entry.key == key)
+                        .map(|entry| entry.value);
+                    let context =
+                        format!("capacity {capacity}, pushes {pushes}, num_iters {num_iters:?}");
+                    for (name, compare) in comparators {
+                        assert_eq!(
+                            changelog.find_latest_composite(key, num_iters, compare),
+                            expected,
+                            "{name}, {context}"
+                        );
+                    }
+                    // Whole 64 byte key
+                    assert_eq!(
+                        changelog.find_latest::<false>(key, num_iters),
+                        expected,
+                        "{context}"
+                    );
+                    assert_eq!(
+                        changelog.find_latest_simd_iterator(key, num_iters),
+                        expected,
+                        "{context}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_masked_and_prefix_eq() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        type CompareFn = fn(&[u8; 32], &[u8; 32]) -> bool;
+        let prefix_comparators: [(usize, CompareFn); 10] = [
+            (0, prefix_eq::<0>),
+            (1, prefix_eq::<1>),
+            (7, prefix_eq::<7>),
+            (8, prefix_eq::<8>),
+            (9, prefix_eq::<9>),
+            (16, prefix_eq::<16>),
+            (20, prefix_eq::<20>),
+            (24, prefix_eq::<24>),
+            (31, prefix_eq::<31>),
+            (32, prefix_eq::<32>),
+        ];
+
+        let mut rng = StdRng::seed_from_u64(0x3a5c);
+        let a: [u8; 32] = rng.gen();
+        for pos in 0..32 {
+            let mut b = a;
+            b[pos] ^= 1 << rng.gen_range(0..8);
+            for (len, compare) in prefix_comparators {
+                assert_eq!(compare(&a, &b), pos >= len, "prefix {len}, diff at {pos}");
+                assert!(compare(&a, &a));
+                assert_eq!(masked_eq(&a, &b, &prefix_mask(len)), pos >= len);
+            }
+
+            let mask: [u8; 32] = rng.gen();
+            assert_eq!(masked_eq(&a, &b, &mask), (a[pos] ^ b[pos]) & mask[pos] == 0);
+            let mut ignore = [0xff; 32];
+            ignore[pos] = 0;
+            assert!(masked_eq(&a, &b, &ignore));
+            assert!(!masked_eq(&a, &b, &[0xff; 32]));
+        }
+        assert_eq!(prefix_mask(40), [0xff; 32]);
+    }
+
+    #[test]
+    fn test_find_latest_by_prefix() {
+        let capacity = 6u64;
+        let mut backing_store =
+            AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
+        let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+
+        // Key of value v differs from the base at byte 28 - 4 * v, newer keys
+        // share a shorter prefix. Values 0 and 1 are overwritten.
+        let base = core::array::from_fn(|i| i as u8 + 1);
+        let mut model = Vec::new();
+        for value in 0..8u64 {
+            let mut mint = base;
+            mint[28 - value as usize * 4] ^= 0x80;
+            changelog.push(Entry::new(mint, value));
+            model.push(Entry::new(mint, value));
+        }
+        let retained = &model[model.len() - capacity as usize..];
+
+        for len in 0..=32 {
+            let prefix = &base[..len];
+            for num_iters in [None, Some(1), Some(3)] {
+                let expected = retained
+                    .iter()
+                    .rev()
+                    .take(num_iters.unwrap_or(usize::MAX))
+                    .find(|entry| entry.mint.starts_with(prefix))
+                    .map(|entry| entry.value);
+                assert_eq!(
+                    changelog.find_latest_by_prefix(prefix, num_iters),
+                    expected,
+                    "prefix {len}, num_iters {num_iters:?}"
+                );
+            }
+        }
+        assert_eq!(changelog.find_latest_by_prefix(&[], None), Some(7));
+        assert_eq!(changelog.find_latest_by_prefix(&base[..1], None), Some(6));
+        assert_eq!(changelog.find_latest_by_prefix(&base[..17], None), Some(2));
+        assert_eq!(changelog.find_latest_by_prefix(&base[..21], None), None);
+        assert_eq!(changelog.find_latest_by_prefix(&[1; 33], None), None);
+
+        // Ignore byte 0, where the newest key differs from the base
+        let mut mask = [0xff; 32];
+        mask[0] = 0;
+        assert_eq!(changelog.find_latest_masked(base, mask, None), Some(7));
+        assert_eq!(changelog.find_latest_masked(base, [0xff; 32], None), None);
+        assert_eq!(
+            changelog.find_latest_entry_with(base, None, prefix_eq::<12>),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn test_bytes_comparators() {
+        type BytesCompare = fn(&[u8], &[u8]) -> bool;
+        let comparators: [(&str, BytesCompare); 9] = [
+            ("bytes", bytes_compare),
+            ("branchless_bytes", branchless_bytes_compare),
+            ("sol_memcmp_bytes", sol_memcmp_bytes_compare),
+            ("chunked_u16", chunked_compare::<u16>),
+            ("chunked_u32", chunked_compare::<u32>),
+            ("chunked_u64", chunked_compare::<u64>),
+            ("chunked_u128", chunked_compare::<u128>),
+            ("pointer_equality_bytes", pointer_equality_bytes_compare),
+            ("combined_fast_bytes", combined_fast_bytes_compare),
+        ];
+        let a: Vec<u8> = (0..40).collect();
+        for len in 0..=a.len() {
+            let a = &a[..len];
+            let copy = a.to_vec();
+            for (name, compare) in comparators {
+                assert!(compare(a, a), "{name} same slice, len {len}");
+                assert!(compare(a, &copy), "{name} equal, len {len}");
+                if len > 0 {
+                    assert!(!compare(a, &a[..len - 1]), "{name} shorter, len {len}");
+                }
+                for pos in 0..len {
+                    let mut b = a.to_vec();
+                    b[pos] ^= 0x80;
+                    assert!(!compare(a, &b), "{name}, len {len}, diff at {pos}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_keys_dispatch() {
+        let key = [7u8; 32];
+        assert_eq!(key_bytes_32(&key), Some(&key));
+        assert_eq!(key_bytes_32(&[7u8; 20]), None);
+
+        // 32 byte keys only reach `compare`, other sizes only `fallback`
+        let compare = |a: &[u8; 32], b: &[u8; 32]| a == b;
+        let unreachable_compare = |_: &[u8; 32], _: &[u8; 32]| -> bool { unreachable!() };
+        let unreachable_fallback = |_: &[u8], _: &[u8]| -> bool { unreachable!() };
+        assert!(compare_keys(&key, &key, compare, unreachable_fallback));
+        assert!(compare_keys(&[1u64; 4], &[1u64; 4], compare, unreachable_fallback));
+        assert!(!compare_keys(&[1u8; 20], &[2u8; 20], unreachable_compare, bytes_compare));
+        assert!(compare_keys(&[1u32; 2], &[1u32; 2], unreachable_compare, bytes_compare));
+    }
+
+    /// Interleaved pushes and lookups checked against a `VecDeque` of the
+    /// retained entries.
+    mod ring_model {
+        use super::*;
+        use crate::strategy::Strategy as Lookup;
+        use proptest::prelude::*;
+        use std::collections::VecDeque;
+
+        /// Pushed keys, `KEYS` itself is never pushed.
+        const KEYS: u8 = 4;
+
+        #[derive(Debug, Clone)]
+        enum Op {
+            Push {
+                key: u8,
+                value: u64,
+            },
+            Find {
+                key: u8,
+                num_iters: Option<usize>,
+                strategy: usize,
+            },
+        }
+
+        /// Keys differ in one byte, in a different u64 chunk per key.
+        fn key(index: u8) -> [u8; 32] {
+            let mut key = [7u8; 32];
+            key[index as usize * 9 % 32] = index;
+            key
+        }
+
+        fn op() -> impl Strategy<Value = Op> {
+            prop_oneof![
+                (0..KEYS, any::<u64>()).prop_map(|(key, value)| Op::Push { key, value }),
+                (
+                    0..=KEYS,
+                    proptest::option::of(0..16usize),
+                    0..Lookup::ALL.len()
+                )
+                    .prop_map(|(key, num_iters, strategy)| Op::Find {
+                        key,
+                        num_iters,
+                        strategy
+                    }),
+            ]
+        }
+
+        fn expected(model: &VecDeque<Entry>, key: [u8; 32], num_iters: Option<usize>) -> Option<u64> {
+            model
+                .iter()
+                .rev()
+                .take(num_iters.unwrap_or(usize::MAX))
+                .find(|entry| entry.mint == key)
+                .map(|entry| entry.value)
+        }
+
+        proptest! {
+            #[test]
+            #[cfg_attr(miri, ignore)]
+            fn test_ring_matches_vecdeque(
+                capacity in prop_oneof![Just(1u64), 1..=12u64],
+                ops in proptest::collection::vec(op(), 0..64),
+            ) {
+                let mut backing_store =
+                    AlignedBytes::zeroed(ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity));
+                let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+                let mut model = VecDeque::new();
+
+                for op in ops {
+                    match op {
+                        Op::Push { key: index, value } => {
+                            changelog.push(Entry::new(key(index), value));
+                            if model.len() == capacity as usize {
+                                model.pop_front();
+                            }
+                            model.push_back(Entry::new(key(index), value));
+                        }
+                        Op::Find { key: index, num_iters, strategy } => {
+                            let strategy = Lookup::ALL[strategy];
+                            let expected = expected(&model, key(index), num_iters);
+                            let view = changelog.view();
+                            prop_assert_eq!(
+                                strategy.lookup_fn()(&view, key(index), num_iters),
+                                expected,
+                                "{}",
+                                strategy.name()
+                            );
+                            prop_assert_eq!(
+                                strategy.entry_lookup_fn()(&view, key(index), num_iters),
+                                expected,
+                                "{} entry fast path",
+                                strategy.name()
+                            );
+                            prop_assert_eq!(
+                                view.find_latest_simd_iterator_with_cu_tracking(
+                                    key(index),
+                                    num_iters,
+                                    CuCheckpoints::default(),
+                                    |_| {}
+                                ),
+                                expected
+                            );
+                        }
+                    }
+
+                    prop_assert_eq!(changelog.len(), model.len());
+                    prop_assert_eq!(changelog.capacity(), capacity as usize);
+                    if let Some(newest) = model.back() {
+                        let view = changelog.view();
+                        let latest = &view.entries[view.entries.last_index()];
+                        prop_assert_eq!((latest.mint, latest.value), (newest.mint, newest.value));
+                    }
+                }
+
+                // The header written by push reloads the same ring
+                let changelog_ref = GenericChangelogRef::<Entry>::from_bytes(&backing_store).unwrap();
+                prop_assert_eq!(changelog_ref.len(), model.len());
+                prop_assert_eq!(changelog_ref.capacity(), capacity as usize);
+                for index in 0..=KEYS {
+                    prop_assert_eq!(
+                        changelog_ref.find_latest_simd_iterator(key(index), None),
+                        expected(&model, key(index), None)
+                    );
+                }
+            }
+        }
+    }
 }
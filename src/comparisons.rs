@@ -131,4 +131,28 @@ fn unsafe_pointer_compare_32(a: &[u8; 32], b: &[u8; 32]) -> bool {
         }
         true
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_32_misaligned() {
+        let buffer: [u8; 48] = core::array::from_fn(|i| i as u8);
+        for offset in 0..16 {
+            let a: &[u8; 32] = buffer[offset..offset + 32].try_into().unwrap();
+            let mut other = *a;
+            for pos in [None, Some(0), Some(17), Some(31)] {
+                if let Some(pos) = pos {
+                    other[pos] ^= 0xff;
+                }
+                let expected = *a == other;
+                assert_eq!(manual_compare_32(a, &other), expected);
+                assert_eq!(unrolled_compare_32(a, &other), expected);
+                assert_eq!(unsafe_pointer_compare_32(a, &other), expected);
+                assert_eq!(unsafe_pointer_compare_32(&other, a), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use light_zero_copy::errors::ZeroCopyError;
+
+/// Errors returned when a changelog account or lookup payload is malformed.
+///
+/// Converted into `ProgramError::Custom(code)`, the code is the discriminant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ChangelogError {
+    /// Capacity passed to `GenericChangelog::new` is zero or out of range.
+    InvalidCapacity = 0,
+    /// Account data is smaller than the header plus `capacity` entries.
+    AccountTooSmall = 1,
+    /// Account header was never written by `GenericChangelog::new`.
+    UninitializedHeader = 2,
+    /// Capacity stored in the header differs from the expected capacity.
+    CapacityMismatch = 3,
+    /// Instruction data does not contain a 32 byte key.
+    InvalidKeyLength = 4,
+    /// Lookup strategy is not in the strategy table.
+    InvalidStrategy = 5,
+    /// Lookup instruction payload is malformed.
+    InvalidInstruction = 6,
+    /// Lookup return data is not a packed lookup result.
+    InvalidReturnData = 7,
+}
+
+impl ChangelogError {
+    #[inline(always)]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangelogError::InvalidCapacity => "Invalid changelog capacity",
+            ChangelogError::AccountTooSmall => "Changelog account too small for capacity",
+            ChangelogError::UninitializedHeader => "Changelog account header is uninitialized",
+            ChangelogError::CapacityMismatch => "Changelog capacity does not match expected",
+            ChangelogError::InvalidKeyLength => "Lookup key must be 32 bytes",
+            ChangelogError::InvalidStrategy => "Unknown lookup strategy",
+            ChangelogError::InvalidInstruction => "Malformed lookup instruction",
+            ChangelogError::InvalidReturnData => "Malformed lookup return data",
+        }
+    }
+
+    /// Logs the error so failures are visible in transaction logs.
+    #[cfg(feature = "solana-program")]
+    #[cold]
+    pub fn print(&self) {
+        solana_program::msg!("ChangelogError {}: {}", *self as u32, self.as_str());
+    }
+}
+
+impl core::fmt::Display for ChangelogError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "solana-program")]
+impl From<ChangelogError> for solana_program::program_error::ProgramError {
+    #[inline(always)]
+    fn from(e: ChangelogError) -> Self {
+        Self::Custom(e as u32)
+    }
+}
+
+#[cfg(feature = "pinocchio")]
+impl From<ChangelogError> for pinocchio::program_error::ProgramError {
+    #[inline(always)]
+    fn from(e: ChangelogError) -> Self {
+        Self::Custom(e as u32)
+    }
+}
+
+impl From<ZeroCopyError> for ChangelogError {
+    fn from(e: ZeroCopyError) -> Self {
+        match e {
+            ZeroCopyError::InsufficientMemoryAllocated(_, _) | ZeroCopyError::Size => {
+                ChangelogError::AccountTooSmall
+            }
+            ZeroCopyError::InvalidCapacity => ChangelogError::InvalidCapacity,
+            // Any other header inconsistency (length > capacity, bad current
+            // index, ...) means the header was not written by `new`.
+            _ => ChangelogError::UninitializedHeader,
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use crate::changelog::{CuCheckpoints, OwnerMint};
+use crate::error::ChangelogError;
+
+/// Opcode of the lookup instruction.
+pub const LOOKUP: u8 = 10;
+
+/// Opcode of the lookup through the `Entry` fast path of the strategy,
+/// same layout as `LOOKUP`.
+pub const LOOKUP_ENTRY: u8 = 12;
+
+/// Opcode of the `simd_iterator` lookup that logs compute units at checkpoints.
+pub const LOOKUP_CU_TRACKING: u8 = 11;
+
+/// Opcode of the (owner, mint) lookup in an `OwnerMintEntry` changelog.
+pub const LOOKUP_COMPOSITE: u8 = 13;
+
+/// First `sol_log_64` argument of every CU checkpoint log,
+/// followed by `entries` and `chunks` of the `CuCheckpoint`.
+pub const CU_CHECKPOINT_TAG: u64 = 0xc0c0;
+
+/// Length of the lookup return data: `[found, value: u64 LE]`.
+pub const LOOKUP_RESULT_LEN: usize = 1 + 8;
+
+/// Encodes a lookup result as return data, the value is 0 if not found.
+#[inline(always)]
+pub fn pack_lookup_result(result: Option<u64>) -> [u8; LOOKUP_RESULT_LEN] {
+    let mut data = [0u8; LOOKUP_RESULT_LEN];
+    if let Some(value) = result {
+        data[0] = 1;
+        data[1..].copy_from_slice(&value.to_le_bytes());
+    }
+    data
+}
+
+/// Decodes the return data of a lookup.
+pub fn unpack_lookup_result(data: &[u8]) -> Result<Option<u64>, ChangelogError> {
+    let data: &[u8; LOOKUP_RESULT_LEN] = data
+        .try_into()
+        .map_err(|_| ChangelogError::InvalidReturnData)?;
+    match data[0] {
+        0 => Ok(None),
+        1 => Ok(Some(u64::from_le_bytes(data[1..].try_into().unwrap()))),
+        _ => Err(ChangelogError::InvalidReturnData),
+    }
+}
+
+#[inline(always)]
+fn unpack_num_iters(has_num_iters: u8, num_iters: &[u8]) -> Result<Option<u32>, ChangelogError> {
+    let num_iters = u32::from_le_bytes(num_iters.try_into().unwrap());
+    match has_num_iters {
+        0 => Ok(None),
+        1 => Ok(Some(num_iters)),
+        _ => Err(ChangelogError::InvalidInstruction),
+    }
+}
+
+/// Lookup in the changelog account with a selectable strategy.
+///
+/// Layout: `[LOOKUP, strategy, has_num_iters, num_iters: u32 LE, key: [u8; 32]]`,
+/// the opcode is `LOOKUP_ENTRY` for the `Entry` fast path.
+/// `num_iters` is always present, it is ignored if `has_num_iters` is 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LookupInstruction {
+    /// Index into [`crate::strategy::LOOKUP_TABLE`], or
+    /// [`crate::strategy::ENTRY_LOOKUP_TABLE`] if `entry_fast_path` is set.
+    pub strategy: u8,
+    /// Maximum number of entries to search, `None` searches all.
+    pub num_iters: Option<u32>,
+    pub key: [u8; 32],
+    /// Sent as `LOOKUP_ENTRY`.
+    pub entry_fast_path: bool,
+}
+
+impl LookupInstruction {
+    pub const LEN: usize = 1 + 1 + 1 + 4 + 32;
+
+    #[inline(always)]
+    pub fn new(strategy: impl Into<u8>, num_iters: Option<u32>, key: [u8; 32]) -> Self {
+        Self {
+            strategy: strategy.into(),
+            num_iters,
+            key,
+            entry_fast_path: false,
+        }
+    }
+
+    /// Lookup through the `Entry` fast path of the strategy.
+    #[inline(always)]
+    pub fn entry_fast_path(mut self) -> Self {
+        self.entry_fast_path = true;
+        self
+    }
+
+    /// Parses the instruction data including the opcode.
+    #[inline(always)]
+    pub fn unpack(instruction_data: &[u8]) -> Result<Self, ChangelogError> {
+        let entry_fast_path = match instruction_data.first() {
+            Some(&LOOKUP) => false,
+            Some(&LOOKUP_ENTRY) => true,
+            _ => return Err(ChangelogError::InvalidInstruction),
+        };
+        if instruction_data.len() < 7 {
+            return Err(ChangelogError::InvalidInstruction);
+        }
+        let strategy = instruction_data[1];
+        let num_iters = unpack_num_iters(instruction_data[2], &instruction_data[3..7])?;
+        let key: [u8; 32] = instruction_data[7..]
+            .try_into()
+            .map_err(|_| ChangelogError::InvalidKeyLength)?;
+        Ok(Self {
+            strategy,
+            num_iters,
+            key,
+            entry_fast_path,
+        })
+    }
+
+    #[cfg(any(feature = "std", test))]
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.push(if self.entry_fast_path { LOOKUP_ENTRY } else { LOOKUP });
+        data.push(self.strategy);
+        data.push(self.num_iters.is_some() as u8);
+        data.extend_from_slice(&self.num_iters.unwrap_or_default().to_le_bytes());
+        data.extend_from_slice(&self.key);
+        data
+    }
+}
+
+/// `simd_iterator` lookup with compute unit checkpoints.
+///
+/// Layout: `[LOOKUP_CU_TRACKING, per_chunk, every_entries: u32 LE, has_num_iters,
+/// num_iters: u32 LE, key: [u8; 32]]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CuTrackingInstruction {
+    pub checkpoints: CuCheckpoints,
+    /// Maximum number of entries to search, `None` searches all.
+    pub num_iters: Option<u32>,
+    pub key: [u8; 32],
+}
+
+impl CuTrackingInstruction {
+    pub const LEN: usize = 1 + 1 + 4 + 1 + 4 + 32;
+
+    /// Parses the instruction data including the opcode.
+    #[inline(always)]
+    pub fn unpack(instruction_data: &[u8]) -> Result<Self, ChangelogError> {
+        if instruction_data.first() != Some(&LOOKUP_CU_TRACKING) {
+            return Err(ChangelogError::InvalidInstruction);
+        }
+        if instruction_data.len() < 11 {
+            return Err(ChangelogError::InvalidInstruction);
+        }
+        let per_chunk = match instruction_data[1] {
+            0 => false,
+            1 => true,
+            _ => return Err(ChangelogError::InvalidInstruction),
+        };
+        let every_entries = u32::from_le_bytes(instruction_data[2..6].try_into().unwrap());
+        let num_iters = unpack_num_iters(instruction_data[6], &instruction_data[7..11])?;
+        let key: [u8; 32] = instruction_data[11..]
+            .try_into()
+            .map_err(|_| ChangelogError::InvalidKeyLength)?;
+        Ok(Self {
+            checkpoints: CuCheckpoints {
+                every_entries,
+                per_chunk,
+            },
+            num_iters,
+            key,
+        })
+    }
+
+    #[cfg(any(feature = "std", test))]
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.push(LOOKUP_CU_TRACKING);
+        data.push(self.checkpoints.per_chunk as u8);
+        data.extend_from_slice(&self.checkpoints.every_entries.to_le_bytes());
+        data.push(self.num_iters.is_some() as u8);
+        data.extend_from_slice(&self.num_iters.unwrap_or_default().to_le_bytes());
+        data.extend_from_slice(&self.key);
+        data
+    }
+}
+
+/// Lookup in an `OwnerMintEntry` changelog with a selectable composite
+/// strategy.
+///
+/// Layout: `[LOOKUP_COMPOSITE, strategy, has_num_iters, num_iters: u32 LE,
+/// owner: [u8; 32], mint: [u8; 32]]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompositeLookupInstruction {
+    /// Index into [`crate::strategy::COMPOSITE_LOOKUP_TABLE`].
+    pub strategy: u8,
+    /// Maximum number of entries to search, `None` searches all.
+    pub num_iters: Option<u32>,
+    pub key: OwnerMint,
+}
+
+impl CompositeLookupInstruction {
+    pub const LEN: usize = 1 + 1 + 1 + 4 + 64;
+
+    #[inline(always)]
+    pub fn new(strategy: impl Into<u8>, num_iters: Option<u32>, key: OwnerMint) -> Self {
+        Self {
+            strategy: strategy.into(),
+            num_iters,
+            key,
+        }
+    }
+
+    /// Parses the instruction data including the opcode.
+    #[inline(always)]
+    pub fn unpack(instruction_data: &[u8]) -> Result<Self, ChangelogError> {
+        if instruction_data.first() != Some(&LOOKUP_COMPOSITE) {
+            return Err(ChangelogError::InvalidInstruction);
+        }
+        if instruction_data.len() < 7 {
+            return Err(ChangelogError::InvalidInstruction);
+        }
+        let strategy = instruction_data[1];
+        let num_iters = unpack_num_iters(instruction_data[2], &instruction_data[3..7])?;
+        let key: &[u8; 64] = instruction_data[7..]
+            .try_into()
+            .map_err(|_| ChangelogError::InvalidKeyLength)?;
+        Ok(Self {
+            strategy,
+            num_iters,
+            key: OwnerMint {
+                owner: key[..32].try_into().unwrap(),
+                mint: key[32..].try_into().unwrap(),
+            },
+        })
+    }
+
+    #[cfg(any(feature = "std", test))]
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.push(LOOKUP_COMPOSITE);
+        data.push(self.strategy);
+        data.push(self.num_iters.is_some() as u8);
+        data.extend_from_slice(&self.num_iters.unwrap_or_default().to_le_bytes());
+        data.extend_from_slice(&self.key.owner);
+        data.extend_from_slice(&self.key.mint);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::Strategy;
+
+    #[test]
+    fn test_lookup_instruction_roundtrip() {
+        for num_iters in [None, Some(0), Some(10), Some(u32::MAX)] {
+            let ix = LookupInstruction::new(Strategy::SimdIterator, num_iters, [7u8; 32]);
+            let data = ix.pack();
+            assert_eq!(data.len(), LookupInstruction::LEN);
+            assert_eq!(LookupInstruction::unpack(&data), Ok(ix));
+
+            let ix = ix.entry_fast_path();
+            let data = ix.pack();
+            assert_eq!(data[0], LOOKUP_ENTRY);
+            assert_eq!(LookupInstruction::unpack(&data), Ok(ix));
+        }
+
+        let mut data = LookupInstruction::new(Strategy::PartialEq, None, [1u8; 32]).pack();
+        data.pop();
+        assert_eq!(
+            LookupInstruction::unpack(&data),
+            Err(ChangelogError::InvalidKeyLength)
+        );
+        data[2] = 2;
+        assert_eq!(
+            LookupInstruction::unpack(&data),
+            Err(ChangelogError::InvalidInstruction)
+        );
+    }
+
+    #[test]
+    fn test_lookup_result_roundtrip() {
+        for result in [None, Some(0), Some(12345), Some(u64::MAX)] {
+            assert_eq!(unpack_lookup_result(&pack_lookup_result(result)), Ok(result));
+        }
+        assert_eq!(
+            unpack_lookup_result(&[]),
+            Err(ChangelogError::InvalidReturnData)
+        );
+        assert_eq!(
+            unpack_lookup_result(&[2; LOOKUP_RESULT_LEN]),
+            Err(ChangelogError::InvalidReturnData)
+        );
+    }
+
+    #[test]
+    fn test_composite_lookup_instruction_roundtrip() {
+        let key = OwnerMint {
+            owner: [1u8; 32],
+            mint: [2u8; 32],
+        };
+        for num_iters in [None, Some(10)] {
+            let ix = CompositeLookupInstruction::new(
+                crate::strategy::CompositeStrategy::FieldByField,
+                num_iters,
+                key,
+            );
+            let data = ix.pack();
+            assert_eq!(data.len(), CompositeLookupInstruction::LEN);
+            assert_eq!(CompositeLookupInstruction::unpack(&data), Ok(ix));
+            assert_eq!(
+                LookupInstruction::unpack(&data),
+                Err(ChangelogError::InvalidInstruction)
+            );
+        }
+
+        let mut data = CompositeLookupInstruction::new(0, None, key).pack();
+        data.pop();
+        assert_eq!(
+            CompositeLookupInstruction::unpack(&data),
+            Err(ChangelogError::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_cu_tracking_instruction_roundtrip() {
+        let ix = CuTrackingInstruction {
+            checkpoints: CuCheckpoints {
+                every_entries: 10,
+                per_chunk: true,
+            },
+            num_iters: Some(100),
+            key: [3u8; 32],
+        };
+        let data = ix.pack();
+        assert_eq!(data.len(), CuTrackingInstruction::LEN);
+        assert_eq!(CuTrackingInstruction::unpack(&data), Ok(ix));
+        assert_eq!(
+            LookupInstruction::unpack(&data),
+            Err(ChangelogError::InvalidInstruction)
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! `sol_memcmp` without depending on a Solana SDK, and 8 byte aligned account
+//! data for host code.
+
+#[cfg(all(target_os = "solana", not(feature = "host-memcmp")))]
+extern "C" {
+    fn sol_memcmp_(s1: *const u8, s2: *const u8, n: u64, result: *mut i32);
+}
+
+/// Compares the first `n` bytes of `s1` and `s2`, like C `memcmp`.
+///
+/// Uses the `sol_memcmp_` syscall on chain and a byte loop on the host, or
+/// everywhere with the `host-memcmp` feature.
+#[inline(always)]
+pub fn sol_memcmp(s1: &[u8], s2: &[u8], n: usize) -> i32 {
+    #[cfg(all(target_os = "solana", not(feature = "host-memcmp")))]
+    {
+        let mut result = 0;
+        unsafe {
+            sol_memcmp_(s1.as_ptr(), s2.as_ptr(), n as u64, &mut result as *mut i32);
+        }
+        result
+    }
+
+    #[cfg(any(not(target_os = "solana"), feature = "host-memcmp"))]
+    {
+        for (a, b) in s1[..n].iter().zip(&s2[..n]) {
+            if a != b {
+                return *a as i32 - *b as i32;
+            }
+        }
+        0
+    }
+}
+
+/// Zeroed 8 byte aligned account data for host side tests, benches and
+/// fuzzing.
+///
+/// The runtime hands programs 8 byte aligned account data, a `Vec<u8>` is
+/// only 1 byte aligned and `from_bytes` rejects misaligned changelogs.
+#[cfg(any(feature = "std", test))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlignedBytes {
+    words: std::vec::Vec<u64>,
+    len: usize,
+}
+
+#[cfg(any(feature = "std", test))]
+impl AlignedBytes {
+    pub fn zeroed(len: usize) -> Self {
+        Self {
+            words: std::vec![0; len.div_ceil(8)],
+            len,
+        }
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        let mut aligned = Self::zeroed(bytes.len());
+        aligned[..].copy_from_slice(bytes);
+        aligned
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl core::ops::Deref for AlignedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &zerocopy::IntoBytes::as_bytes(self.words.as_slice())[..self.len]
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl core::ops::DerefMut for AlignedBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut zerocopy::IntoBytes::as_mut_bytes(self.words.as_mut_slice())[..self.len]
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "bench-variants")]
+use crate::changelog::CuCheckpoint;
+#[cfg(feature = "bench-variants")]
+use crate::changelog::OwnerMintEntry;
+use crate::changelog::{Entry, GenericChangelogRef};
+use crate::error::ChangelogError;
+#[cfg(feature = "bench-variants")]
+use crate::instruction::{CompositeLookupInstruction, CuTrackingInstruction};
+use crate::instruction::LookupInstruction;
+#[cfg(feature = "bench-variants")]
+use crate::strategy::CompositeStrategy;
+use crate::strategy::Strategy;
+
+/// Runs a `LookupInstruction` against changelog account data.
+///
+/// Framework independent, the entrypoint adapters only borrow the account data.
+#[inline(always)]
+pub fn process_lookup(
+    instruction_data: &[u8],
+    changelog_data: &[u8],
+) -> Result<Option<u64>, ChangelogError> {
+    let instruction = LookupInstruction::unpack(instruction_data)?;
+    let strategy = Strategy::try_from(instruction.strategy)?;
+    let changelog: GenericChangelogRef<'_, Entry> =
+        GenericChangelogRef::from_bytes(changelog_data)?;
+    lookup(
+        strategy,
+        instruction.entry_fast_path,
+        &changelog,
+        instruction.key,
+        instruction.num_iters.map(|n| n as usize),
+    )
+}
+
+/// Runs a `CuTrackingInstruction`, `checkpoint` is called at every checkpoint
+/// of the scan.
+///
+/// Two checkpoints are emitted back to back before the scan, their difference
+/// is the cost of one checkpoint.
+#[cfg(feature = "bench-variants")]
+#[inline(always)]
+pub fn process_lookup_with_cu_tracking(
+    instruction_data: &[u8],
+    changelog_data: &[u8],
+    mut checkpoint: impl FnMut(CuCheckpoint),
+) -> Result<Option<u64>, ChangelogError> {
+    let instruction = CuTrackingInstruction::unpack(instruction_data)?;
+    let changelog: GenericChangelogRef<'_, Entry> =
+        GenericChangelogRef::from_bytes(changelog_data)?;
+    checkpoint(CuCheckpoint::entry(0));
+    Ok(changelog.find_latest_simd_iterator_with_cu_tracking(
+        instruction.key,
+        instruction.num_iters.map(|n| n as usize),
+        instruction.checkpoints,
+        checkpoint,
+    ))
+}
+
+/// Runs a `CompositeLookupInstruction` against `OwnerMintEntry` changelog
+/// account data.
+#[cfg(feature = "bench-variants")]
+#[inline(always)]
+pub fn process_composite_lookup(
+    instruction_data: &[u8],
+    changelog_data: &[u8],
+) -> Result<Option<u64>, ChangelogError> {
+    let instruction = CompositeLookupInstruction::unpack(instruction_data)?;
+    let strategy = CompositeStrategy::try_from(instruction.strategy)?;
+    let changelog: GenericChangelogRef<'_, OwnerMintEntry> =
+        GenericChangelogRef::from_bytes(changelog_data)?;
+    Ok(strategy.lookup_fn()(
+        &changelog,
+        instruction.key,
+        instruction.num_iters.map(|n| n as usize),
+    ))
+}
+
+/// Dispatches to any strategy through the lookup table.
+#[cfg(feature = "bench-variants")]
+#[inline(always)]
+fn lookup(
+    strategy: Strategy,
+    entry_fast_path: bool,
+    changelog: &GenericChangelogRef<'_, Entry>,
+    key: [u8; 32],
+    num_iters: Option<usize>,
+) -> Result<Option<u64>, ChangelogError> {
+    let lookup = if entry_fast_path {
+        strategy.entry_lookup_fn()
+    } else {
+        strategy.lookup_fn()
+    };
+    Ok(lookup(changelog, key, num_iters))
+}
+
+/// Only `PRODUCTION_STRATEGY` is compiled in, its lookups are resolved from
+/// the tables at compile time.
+#[cfg(not(feature = "bench-variants"))]
+#[inline(always)]
+fn lookup(
+    strategy: Strategy,
+    entry_fast_path: bool,
+    changelog: &GenericChangelogRef<'_, Entry>,
+    key: [u8; 32],
+    num_iters: Option<usize>,
+) -> Result<Option<u64>, ChangelogError> {
+    use crate::strategy::PRODUCTION_STRATEGY;
+
+    if strategy != PRODUCTION_STRATEGY {
+        return Err(ChangelogError::InvalidStrategy);
+    }
+    let lookup = if entry_fast_path {
+        const { PRODUCTION_STRATEGY.entry_lookup_fn() }
+    } else {
+        const { PRODUCTION_STRATEGY.lookup_fn() }
+    };
+    Ok(lookup(changelog, key, num_iters))
+}
@@ -0,0 +1,267 @@
+use crate::changelog::{
+    branchless_compare, combined_fast_compare, pointer_equality_compare, simd_iterator_compare,
+    simd_iterator_u128_compare, simd_iterator_u16_compare, simd_iterator_u32_compare,
+    simd_slice_compare, simd_style_compare, simd_zip_compare, sol_memcmp_compare,
+    u128_cast_compare, unrolled_compare, unsafe_fast_compare, Entry, GenericChangelogRef,
+    ManualCompare, OwnerMint, OwnerMintEntry,
+};
+use crate::error::ChangelogError;
+
+/// Signature shared by all `find_latest_*` lookups on an `Entry` changelog.
+pub type LookupFn =
+    for<'a> fn(&GenericChangelogRef<'a, Entry>, [u8; 32], Option<usize>) -> Option<u64>;
+
+/// Strategy compiled into the `production` build.
+pub const PRODUCTION_STRATEGY: Strategy = Strategy::SimdIterator;
+
+/// Lookup strategies, the discriminant indexes [`LOOKUP_TABLE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Strategy {
+    PartialEq = 0,
+    Manual = 1,
+    Unrolled = 2,
+    Simd = 3,
+    Branchless = 4,
+    Unsafe = 5,
+    SolMemcmp = 6,
+    U128Cast = 7,
+    PointerEquality = 8,
+    CombinedFast = 9,
+    SimdIterator = 10,
+    SimdZip = 11,
+    SimdSlice = 12,
+    SimdIteratorU16 = 13,
+    SimdIteratorU32 = 14,
+    SimdIteratorU128 = 15,
+    DirectFieldAccess = 16,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 17] = [
+        Strategy::PartialEq,
+        Strategy::Manual,
+        Strategy::Unrolled,
+        Strategy::Simd,
+        Strategy::Branchless,
+        Strategy::Unsafe,
+        Strategy::SolMemcmp,
+        Strategy::U128Cast,
+        Strategy::PointerEquality,
+        Strategy::CombinedFast,
+        Strategy::SimdIterator,
+        Strategy::SimdZip,
+        Strategy::SimdSlice,
+        Strategy::SimdIteratorU16,
+        Strategy::SimdIteratorU32,
+        Strategy::SimdIteratorU128,
+        Strategy::DirectFieldAccess,
+    ];
+
+    /// Name used in benchmark output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::PartialEq => "partialeq",
+            Strategy::Manual => "manual",
+            Strategy::Unrolled => "unrolled",
+            Strategy::Simd => "simd",
+            Strategy::Branchless => "branchless",
+            Strategy::Unsafe => "unsafe",
+            Strategy::SolMemcmp => "sol_memcmp",
+            Strategy::U128Cast => "u128_cast",
+            Strategy::PointerEquality => "pointer_equality",
+            Strategy::CombinedFast => "combined_fast",
+            Strategy::SimdIterator => "simd_iterator",
+            Strategy::SimdZip => "simd_zip",
+            Strategy::SimdSlice => "simd_slice",
+            Strategy::SimdIteratorU16 => "simd_iterator_u16",
+            Strategy::SimdIteratorU32 => "simd_iterator_u32",
+            Strategy::SimdIteratorU128 => "simd_iterator_u128",
+            Strategy::DirectFieldAccess => "direct_field_access",
+        }
+    }
+
+    #[inline(always)]
+    pub const fn lookup_fn(&self) -> LookupFn {
+        LOOKUP_TABLE[*self as usize]
+    }
+
+    /// Same comparator as `lookup_fn` on `entry.mint` in place.
+    #[inline(always)]
+    pub const fn entry_lookup_fn(&self) -> LookupFn {
+        ENTRY_LOOKUP_TABLE[*self as usize]
+    }
+}
+
+impl From<Strategy> for u8 {
+    #[inline(always)]
+    fn from(strategy: Strategy) -> u8 {
+        strategy as u8
+    }
+}
+
+impl TryFrom<u8> for Strategy {
+    type Error = ChangelogError;
+
+    #[inline(always)]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Strategy::ALL
+            .get(value as usize)
+            .copied()
+            .ok_or(ChangelogError::InvalidStrategy)
+    }
+}
+
+/// Lookup for every strategy, indexed by `Strategy as u8`.
+pub const LOOKUP_TABLE: [LookupFn; 17] = [
+    |c, key, n| c.find_latest::<false>(key, n),
+    |c, key, n| c.find_latest::<true>(key, n),
+    |c, key, n| c.find_latest_unrolled(key, n),
+    |c, key, n| c.find_latest_simd(key, n),
+    |c, key, n| c.find_latest_branchless(key, n),
+    |c, key, n| c.find_latest_unsafe(key, n),
+    |c, key, n| c.find_latest_sol_memcmp(key, n),
+    |c, key, n| c.find_latest_u128_cast(key, n),
+    |c, key, n| c.find_latest_pointer_equality(key, n),
+    |c, key, n| c.find_latest_combined_fast(key, n),
+    |c, key, n| c.find_latest_simd_iterator(key, n),
+    |c, key, n| c.find_latest_simd_zip(key, n),
+    |c, key, n| c.find_latest_simd_slice(key, n),
+    |c, key, n| c.find_latest_simd_iterator_u16(key, n),
+    |c, key, n| c.find_latest_simd_iterator_u32(key, n),
+    |c, key, n| c.find_latest_simd_iterator_u128(key, n),
+    |c, key, n| c.find_latest_direct_field_access(key, n),
+];
+
+/// Signature of the `[u8; 32]` comparators.
+pub type CompareFn = fn(&[u8; 32], &[u8; 32]) -> bool;
+
+/// Defines `COMPARATORS` and `ENTRY_LOOKUP_TABLE` from one list, so every
+/// `Entry` fast path uses its own comparator, not a function pointer.
+macro_rules! comparator_tables {
+    ($($strategy:ident => $compare:expr,)*) => {
+        /// Comparator of every strategy except `DirectFieldAccess`, indexed by
+        /// `Strategy as u8`.
+        pub const COMPARATORS: [(Strategy, CompareFn); 16] =
+            [$((Strategy::$strategy, $compare),)*];
+
+        /// `Entry` fast path of every strategy, compares `entry.mint` without the
+        /// `KeyValue::key` copy. Indexed by `Strategy as u8`.
+        pub const ENTRY_LOOKUP_TABLE: [LookupFn; 17] = [
+            $(|c, key, n| c.find_latest_entry_with(key, n, $compare),)*
+            |c, key, n| c.find_latest_direct_field_access(key, n),
+        ];
+    };
+}
+
+comparator_tables! {
+    PartialEq => |a, b| a == b,
+    Manual => |a, b| a.manual_compare(b),
+    Unrolled => unrolled_compare,
+    Simd => simd_style_compare,
+    Branchless => branchless_compare,
+    Unsafe => unsafe_fast_compare,
+    SolMemcmp => sol_memcmp_compare,
+    U128Cast => u128_cast_compare,
+    PointerEquality => pointer_equality_compare,
+    CombinedFast => combined_fast_compare,
+    SimdIterator => simd_iterator_compare,
+    SimdZip => simd_zip_compare,
+    SimdSlice => simd_slice_compare,
+    SimdIteratorU16 => simd_iterator_u16_compare,
+    SimdIteratorU32 => simd_iterator_u32_compare,
+    SimdIteratorU128 => simd_iterator_u128_compare,
+}
+
+/// Signature of the lookups on an `OwnerMintEntry` changelog.
+pub type CompositeLookupFn =
+    for<'a> fn(&GenericChangelogRef<'a, OwnerMintEntry>, OwnerMint, Option<usize>) -> Option<u64>;
+
+/// Lookups of a composite (owner, mint) key, the discriminant indexes
+/// [`COMPOSITE_LOOKUP_TABLE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CompositeStrategy {
+    /// `find_latest_composite`, mint then owner with `simd_iterator_compare`.
+    FieldByField = 0,
+    /// `find_latest_simd_iterator` on the whole 64 byte key.
+    WholeKeySimdIterator = 1,
+    /// `find_latest::<false>` on the whole 64 byte key.
+    WholeKeyPartialEq = 2,
+}
+
+impl CompositeStrategy {
+    pub const ALL: [CompositeStrategy; 3] = [
+        CompositeStrategy::FieldByField,
+        CompositeStrategy::WholeKeySimdIterator,
+        CompositeStrategy::WholeKeyPartialEq,
+    ];
+
+    /// Name used in benchmark output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompositeStrategy::FieldByField => "composite_field_by_field",
+            CompositeStrategy::WholeKeySimdIterator => "composite_whole_key_simd_iterator",
+            CompositeStrategy::WholeKeyPartialEq => "composite_whole_key_partialeq",
+        }
+    }
+
+    #[inline(always)]
+    pub const fn lookup_fn(&self) -> CompositeLookupFn {
+        COMPOSITE_LOOKUP_TABLE[*self as usize]
+    }
+}
+
+impl From<CompositeStrategy> for u8 {
+    #[inline(always)]
+    fn from(strategy: CompositeStrategy) -> u8 {
+        strategy as u8
+    }
+}
+
+impl TryFrom<u8> for CompositeStrategy {
+    type Error = ChangelogError;
+
+    #[inline(always)]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        CompositeStrategy::ALL
+            .get(value as usize)
+            .copied()
+            .ok_or(ChangelogError::InvalidStrategy)
+    }
+}
+
+/// Lookup for every composite strategy, indexed by `CompositeStrategy as u8`.
+pub const COMPOSITE_LOOKUP_TABLE: [CompositeLookupFn; 3] = [
+    |c, key, n| c.find_latest_composite(key, n, simd_iterator_compare),
+    |c, key, n| c.find_latest_simd_iterator(key, n),
+    |c, key, n| c.find_latest::<false>(key, n),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_table_order() {
+        for (i, strategy) in Strategy::ALL.iter().enumerate() {
+            assert_eq!(*strategy as usize, i);
+            assert_eq!(Strategy::try_from(i as u8), Ok(*strategy));
+        }
+        assert_eq!(
+            Strategy::try_from(Strategy::ALL.len() as u8),
+            Err(ChangelogError::InvalidStrategy)
+        );
+        for (i, (strategy, _)) in COMPARATORS.iter().enumerate() {
+            assert_eq!(*strategy as usize, i);
+        }
+        for (i, strategy) in CompositeStrategy::ALL.iter().enumerate() {
+            assert_eq!(*strategy as usize, i);
+            assert_eq!(CompositeStrategy::try_from(i as u8), Ok(*strategy));
+        }
+        assert_eq!(
+            CompositeStrategy::try_from(CompositeStrategy::ALL.len() as u8),
+            Err(ChangelogError::InvalidStrategy)
+        );
+    }
+}
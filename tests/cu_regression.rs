@@ -0,0 +1,130 @@
+//! Compares mollusk CU of the `changelog_cu` scenarios against `bench/cu_baseline.csv`.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo test --test cu_regression -- --ignored --nocapture
+//! # Intentional change, rewrite the baseline:
+//! UPDATE_CU_BASELINE=1 cargo test --test cu_regression -- --ignored
+//! ```
+//!
+//! Baseline rows are `name,cu,tolerance`. A scenario fails when its CU exceeds
+//! `cu + tolerance`. Rewriting keeps existing tolerances.
+#[path = "../benches/bench_support/mod.rs"]
+mod bench_support;
+
+use {
+    bench_support::{changelog_cu_scenarios, mollusk},
+    std::{collections::BTreeMap, fmt::Write},
+};
+
+const BASELINE_PATH: &str = "bench/cu_baseline.csv";
+const HEADER: &str = "name,cu,tolerance";
+
+/// Tolerance for scenarios new to the baseline, in percent of their CU.
+const DEFAULT_TOLERANCE_PERCENT: u64 = 1;
+
+struct Baseline {
+    cu: u64,
+    tolerance: u64,
+}
+
+fn read_baseline() -> BTreeMap<String, Baseline> {
+    let Ok(csv) = std::fs::read_to_string(BASELINE_PATH) else {
+        return BTreeMap::new();
+    };
+    csv.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && *line != HEADER)
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [name, cu, tolerance] = fields[..] else {
+                panic!("{BASELINE_PATH}: expected `{HEADER}`, got `{line}`");
+            };
+            let parse = |v: &str| {
+                v.parse::<u64>()
+                    .unwrap_or_else(|e| panic!("{BASELINE_PATH}: `{line}`: {e}"))
+            };
+            let baseline = Baseline {
+                cu: parse(cu),
+                tolerance: parse(tolerance),
+            };
+            (name.to_string(), baseline)
+        })
+        .collect()
+}
+
+fn write_baseline(measured: &[(String, u64)], baseline: &BTreeMap<String, Baseline>) {
+    let mut csv = format!("{HEADER}\n");
+    for (name, cu) in measured {
+        let tolerance = baseline
+            .get(name)
+            .map_or(cu * DEFAULT_TOLERANCE_PERCENT / 100, |b| b.tolerance);
+        writeln!(csv, "{name},{cu},{tolerance}").unwrap();
+    }
+    std::fs::write(BASELINE_PATH, csv).unwrap();
+}
+
+#[test]
+#[ignore = "requires cargo build-sbf output"]
+fn test_cu_regression() {
+    solana_logger::setup_with("");
+
+    let mollusk = mollusk();
+    let measured: Vec<(String, u64)> = changelog_cu_scenarios()
+        .iter()
+        .map(|scenario| {
+            let case = scenario.build();
+            let cu = case.run(&mollusk);
+            (case.name, cu)
+        })
+        .collect();
+
+    let baseline = read_baseline();
+    if std::env::var_os("UPDATE_CU_BASELINE").is_some() {
+        write_baseline(&measured, &baseline);
+        println!("Rewrote {BASELINE_PATH} with {} scenarios", measured.len());
+        return;
+    }
+    assert!(
+        !baseline.is_empty(),
+        "{BASELINE_PATH} has no measured scenarios, generate it with \
+         `UPDATE_CU_BASELINE=1 cargo test --test cu_regression -- --ignored`"
+    );
+
+    let mut report = String::from("| Name | Baseline | Current | Delta | Tolerance | Status |\n");
+    report.push_str("|------|----------|---------|-------|-----------|--------|\n");
+    let mut failures = 0;
+    for (name, cu) in &measured {
+        let Some(b) = baseline.get(name) else {
+            failures += 1;
+            writeln!(report, "| {name} | - | {cu} | - | - | MISSING |").unwrap();
+            continue;
+        };
+        let status = if *cu > b.cu + b.tolerance {
+            failures += 1;
+            "REGRESSED"
+        } else if *cu + b.tolerance < b.cu {
+            "improved"
+        } else {
+            "ok"
+        };
+        let delta = *cu as i64 - b.cu as i64;
+        writeln!(
+            report,
+            "| {name} | {} | {cu} | {delta:+} | {} | {status} |",
+            b.cu, b.tolerance
+        )
+        .unwrap();
+    }
+    for (name, b) in &baseline {
+        if !measured.iter().any(|(n, _)| n == name) {
+            writeln!(report, "| {name} | {} | - | - | - | stale |", b.cu).unwrap();
+        }
+    }
+
+    println!("{report}");
+    assert!(
+        failures == 0,
+        "{failures} scenario(s) regressed or are missing from {BASELINE_PATH}:\n\n{report}\n\
+         If the change is intentional run `UPDATE_CU_BASELINE=1 cargo test --test cu_regression -- --ignored`"
+    );
+}
@@ -0,0 +1,103 @@
+//! Records `.so` size and lookup CU for the bench and production builds.
+//!
+//! ```bash
+//! cargo build-sbf
+//! cargo build-sbf --no-default-features --features production,solana-program --sbf-out-dir target/deploy/production
+//! cargo test --test program_size -- --ignored --nocapture
+//! ```
+//!
+//! Output: `target/benches/program_size.md`
+use {
+    light_zero_copy::cyclic_vec::ZeroCopyCyclicVecU64,
+    mollusk_svm::Mollusk,
+    optimize_cmp::{
+        changelog::{Entry, GenericChangelog},
+        instruction::{unpack_lookup_result, LookupInstruction},
+        memory::AlignedBytes,
+        strategy::PRODUCTION_STRATEGY,
+    },
+    solana_account::Account,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    std::fmt::Write,
+};
+
+const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0x10, 0x11, 0x12,
+    0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20,
+]);
+
+const BUILDS: [(&str, &str); 2] = [
+    ("bench-variants", "./target/deploy/optimize_cmp"),
+    ("production", "./target/deploy/production/optimize_cmp"),
+];
+
+/// 100 entries, the target key is the 10th newest entry with value 90.
+fn create_changelog_account() -> ([u8; 32], Account) {
+    let capacity = 100u64;
+    let mut backing_store = AlignedBytes::zeroed(
+        ZeroCopyCyclicVecU64::<Entry>::required_size_for_capacity(capacity),
+    );
+    let mut changelog = GenericChangelog::new(capacity, &mut backing_store).unwrap();
+    let target_key = [0xffu8; 32];
+    for i in 0..capacity {
+        let mut mint = [0u8; 32];
+        mint[..8].copy_from_slice(&i.to_le_bytes());
+        if i == capacity - 10 {
+            mint = target_key;
+        }
+        changelog.push(Entry::new(mint, i));
+    }
+    let account = Account {
+        lamports: 0,
+        data: backing_store.to_vec(),
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    (target_key, account)
+}
+
+#[test]
+#[ignore = "requires both cargo build-sbf outputs"]
+fn test_program_size() {
+    solana_logger::setup_with("");
+
+    let changelog_pubkey = Pubkey::new_unique();
+    let (target_key, account) = create_changelog_account();
+    let accounts = vec![(changelog_pubkey, account)];
+    let instruction = Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &LookupInstruction::new(PRODUCTION_STRATEGY, Some(10), target_key).pack(),
+        vec![AccountMeta::new_readonly(changelog_pubkey, false)],
+    );
+
+    let mut report = String::from("| Build | .so size (bytes) | CUs |\n|-------|------|-----|\n");
+    let mut sizes = Vec::new();
+    for (name, path) in BUILDS {
+        let size = std::fs::metadata(format!("{path}.so"))
+            .unwrap_or_else(|e| panic!("{path}.so: {e}"))
+            .len();
+        let mollusk = Mollusk::new(&PROGRAM_ID, path);
+        let result = mollusk.process_instruction(&instruction, &accounts);
+        assert!(result.raw_result.is_ok(), "{name}: {:?}", result.raw_result);
+        assert_eq!(unpack_lookup_result(&result.return_data), Ok(Some(90)));
+        writeln!(
+            report,
+            "| {name} | {size} | {} |",
+            result.compute_units_consumed
+        )
+        .unwrap();
+        sizes.push(size);
+    }
+
+    println!("{report}");
+    std::fs::create_dir_all("target/benches").unwrap();
+    std::fs::write("target/benches/program_size.md", report).unwrap();
+    assert!(
+        sizes[1] < sizes[0],
+        "production build should be smaller than the bench build"
+    );
+}
@@ -0,0 +1,170 @@
+//! Comparators and lookups on aligned, misaligned and in-account keys.
+//!
+//! Runs on the host and under Miri, which reports alignment and provenance
+//! UB in the pointer casting comparators:
+//!
+//! ```bash
+//! cargo +nightly miri test --features host-memcmp --lib --test unsafe_comparators
+//! ```
+use optimize_cmp::{
+    changelog::{
+        masked_eq, prefix_eq, prefix_mask, simd_iterator_compare_with_checkpoints, CuCheckpoints,
+        Entry, GenericChangelog, GenericChangelogRef,
+    },
+    memory::{sol_memcmp, AlignedBytes},
+    strategy::{Strategy, COMPARATORS},
+};
+
+/// Byte positions that end or start a u16/u32/u64/u128 chunk.
+const DIFF_POSITIONS: [usize; 7] = [0, 7, 8, 15, 16, 24, 31];
+
+/// 16 byte aligned, so offset 0 is aligned for every chunk width.
+#[repr(C, align(16))]
+struct Aligned([u8; 48]);
+
+fn key(seed: u8) -> [u8; 32] {
+    core::array::from_fn(|i| seed.wrapping_mul(31).wrapping_add(i as u8))
+}
+
+fn key_at(buffer: &[u8], offset: usize) -> &[u8; 32] {
+    buffer[offset..offset + 32].try_into().unwrap()
+}
+
+fn assert_comparators(a: &[u8; 32], b: &[u8; 32], context: &str) {
+    for (strategy, compare) in COMPARATORS {
+        assert_eq!(compare(a, b), a == b, "{}, {context}", strategy.name());
+    }
+    assert_eq!(
+        simd_iterator_compare_with_checkpoints(a, b, |_| {}),
+        a == b,
+        "simd_iterator_checkpoints, {context}"
+    );
+}
+
+#[test]
+fn test_comparators_aligned_and_misaligned() {
+    let base = key(7);
+    let mut a = Aligned([0; 48]);
+    let mut b = Aligned([0; 48]);
+    for a_offset in 0..16 {
+        for b_offset in [0, a_offset, 15 - a_offset] {
+            a.0[a_offset..a_offset + 32].copy_from_slice(&base);
+            let lhs = key_at(&a.0, a_offset);
+            assert_comparators(lhs, lhs, &format!("same reference, offset {a_offset}"));
+
+            for diff in [None].into_iter().chain(DIFF_POSITIONS.map(Some)) {
+                let mut other = base;
+                if let Some(pos) = diff {
+                    other[pos] ^= 0x80;
+                }
+                b.0[b_offset..b_offset + 32].copy_from_slice(&other);
+                assert_comparators(
+                    key_at(&a.0, a_offset),
+                    key_at(&b.0, b_offset),
+                    &format!("offsets {a_offset}/{b_offset}, diff at {diff:?}"),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_partial_comparators_misaligned() {
+    let base = key(3);
+    let mut a = Aligned([0; 48]);
+    let mut b = Aligned([0; 48]);
+    let mut mask = Aligned([0; 48]);
+    for offset in 0..16 {
+        a.0[offset..offset + 32].copy_from_slice(&base);
+        mask.0[15 - offset..47 - offset].copy_from_slice(&prefix_mask(9));
+        for pos in DIFF_POSITIONS {
+            let mut other = base;
+            other[pos] ^= 0x80;
+            b.0[15 - offset..47 - offset].copy_from_slice(&other);
+            let (lhs, rhs) = (key_at(&a.0, offset), key_at(&b.0, 15 - offset));
+            let context = format!("offset {offset}, diff at {pos}");
+            assert_eq!(
+                masked_eq(lhs, rhs, key_at(&mask.0, 15 - offset)),
+                pos >= 9,
+                "{context}"
+            );
+            assert_eq!(prefix_eq::<9>(lhs, rhs), pos >= 9, "{context}");
+            assert_eq!(prefix_eq::<16>(lhs, rhs), pos >= 16, "{context}");
+        }
+    }
+}
+
+#[test]
+fn test_comparators_on_entries_in_account() {
+    let capacity = 8;
+    let pushes = 12u8;
+    // Account data is 8 byte aligned, keys start at 32 + 40 * i
+    let mut account = AlignedBytes::zeroed(24 + capacity as usize * core::mem::size_of::<Entry>());
+    let mut changelog = GenericChangelog::<Entry>::new(capacity, &mut account).unwrap();
+    for i in 0..pushes {
+        changelog.push(Entry::new(key(i), i as u64));
+    }
+
+    let changelog_ref = GenericChangelogRef::<Entry>::from_bytes(&account).unwrap();
+    for (i, entry) in changelog_ref.entries.iter().enumerate() {
+        for other in changelog_ref.entries.iter() {
+            assert_comparators(&entry.mint, &other.mint, &format!("entry {i}"));
+        }
+        let lookup = key(i as u8);
+        assert_comparators(&entry.mint, &lookup, &format!("entry {i} vs stack key"));
+        assert_comparators(&lookup, &entry.mint, &format!("stack key vs entry {i}"));
+    }
+
+    for i in 0..pushes + 2 {
+        let expected = (i >= pushes - capacity as u8 && i < pushes).then_some(i as u64);
+        for strategy in Strategy::ALL {
+            assert_eq!(
+                strategy.lookup_fn()(&changelog_ref, key(i), None),
+                expected,
+                "{} key {i}",
+                strategy.name()
+            );
+        }
+        let checkpoints = CuCheckpoints {
+            every_entries: 1,
+            per_chunk: true,
+        };
+        assert_eq!(
+            changelog_ref.find_latest_simd_iterator_with_cu_tracking(
+                key(i),
+                None,
+                checkpoints,
+                |_| {}
+            ),
+            expected,
+            "cu_tracking key {i}"
+        );
+    }
+}
+
+#[test]
+fn test_misaligned_account_is_rejected() {
+    let capacity = 4;
+    let size = 24 + capacity as usize * core::mem::size_of::<Entry>();
+    let mut account = AlignedBytes::zeroed(size + 8);
+    GenericChangelog::<Entry>::new(capacity, &mut account[..size]).unwrap();
+
+    for offset in 1..8 {
+        let bytes = &mut account[offset..offset + size];
+        assert!(GenericChangelogRef::<Entry>::from_bytes(bytes).is_err());
+        assert!(GenericChangelog::<Entry>::from_bytes(bytes).is_err());
+    }
+}
+
+#[test]
+fn test_sol_memcmp_misaligned() {
+    let a = Aligned([1; 48]);
+    let mut b = Aligned([1; 48]);
+    b.0[20] = 2;
+    for offset in 0..16 {
+        let (lhs, rhs) = (&a.0[offset..offset + 32], &b.0[1..33]);
+        assert_eq!(sol_memcmp(lhs, rhs, 19), 0);
+        assert_eq!(sol_memcmp(lhs, rhs, 20), -1);
+        assert_eq!(sol_memcmp(rhs, lhs, 32), 1);
+    }
+}